@@ -0,0 +1,96 @@
+//! Procedural macros parsing regular expressions at compile time, with the
+//! grammar of glushkovizer, so that syntax errors become compile errors
+//!
+//! ```compile_fail
+//! let r = glushkovizer_macros::glushkov!("(a+b");
+//! ```
+
+use glushkovizer::automata::{Automata, Transform};
+use glushkovizer::regexp::RegExp;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, LitStr};
+
+/// Expands to the [RegExp]<[char]> described by the string literal, parsed at
+/// compile time
+///
+/// ```
+/// use glushkovizer::regexp::RegExp;
+/// use glushkovizer_macros::glushkov;
+///
+/// let r: RegExp<char> = glushkov!("(a+b)*.a");
+/// assert_eq!(RegExp::try_from("(a+b)*.a").unwrap(), r);
+/// ```
+#[proc_macro]
+pub fn glushkov(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as LitStr);
+    match parse(&lit) {
+        Ok(reg) => regexp(&reg).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Expands to the [DenseDfa](glushkovizer::automata::DenseDfa)<[char]> of
+/// the minimal deterministic automaton of the regular expression described by
+/// the string literal, computed at compile time
+///
+/// ```
+/// use glushkovizer_macros::glushkov_dfa;
+///
+/// let d = glushkov_dfa!("(a+b)*.a");
+/// assert!(d.accept(['b', 'a'].iter()));
+/// ```
+#[proc_macro]
+pub fn glushkov_dfa(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as LitStr);
+    let reg = match parse(&lit) {
+        Ok(reg) => reg,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let dfa = Automata::from(reg).compile();
+    let symbols = dfa.symbols();
+    let table = dfa.table();
+    let finals = dfa.finals();
+    quote! {
+        ::glushkovizer::automata::DenseDfa::from_raw_parts(
+            ::std::vec![#(#symbols),*],
+            ::std::vec![#(#table),*],
+            ::std::vec![#(#finals),*],
+        )
+        .expect("table computed by glushkov_dfa!")
+    }
+    .into()
+}
+
+/// Parses the regular expression of the string literal "lit", the error is
+/// located on the literal and its message gives the line and column
+fn parse(lit: &LitStr) -> Result<RegExp<char>, syn::Error> {
+    RegExp::try_from(lit.value()).map_err(|err| syn::Error::new(lit.span(), err))
+}
+
+/// Returns the expression building "reg"
+fn regexp(reg: &RegExp<char>) -> TokenStream2 {
+    match reg {
+        RegExp::Epsilon => quote!(::glushkovizer::regexp::RegExp::Epsilon),
+        RegExp::Symbol(c) => quote!(::glushkovizer::regexp::RegExp::Symbol(#c)),
+        RegExp::Repeat(e) => {
+            let e = regexp(e);
+            quote!(::glushkovizer::regexp::RegExp::Repeat(::std::boxed::Box::new(#e)))
+        }
+        RegExp::Concat(l, r) => {
+            let (l, r) = (regexp(l), regexp(r));
+            quote!(::glushkovizer::regexp::RegExp::Concat(
+                ::std::boxed::Box::new(#l),
+                ::std::boxed::Box::new(#r)
+            ))
+        }
+        RegExp::Or(l, r) => {
+            let (l, r) = (regexp(l), regexp(r));
+            quote!(::glushkovizer::regexp::RegExp::Or(
+                ::std::boxed::Box::new(#l),
+                ::std::boxed::Box::new(#r)
+            ))
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use glushkovizer::prelude::*;
+use glushkovizer::{automata::Automata, regexp::RegExp};
+use glushkovizer_macros::{glushkov, glushkov_dfa};
+
+#[test]
+fn regexp() {
+    assert_eq!(RegExp::try_from("(a+b)*.a").unwrap(), glushkov!("(a+b)*.a"));
+    assert_eq!(RegExp::try_from("$").unwrap(), glushkov!("$"));
+    assert_eq!(
+        RegExp::try_from("'+'.(b.$+<'>)*").unwrap(),
+        glushkov!("'+'.(b.$+<'>)*")
+    );
+    assert_eq!(RegExp::try_from("é.ü*").unwrap(), glushkov!("é.ü*"));
+}
+
+#[test]
+fn dfa() {
+    let d = glushkov_dfa!("(a+b)*.a.(a+b)");
+    let a = Automata::from(RegExp::try_from("(a+b)*.a.(a+b)").unwrap());
+    assert_eq!(a.compile(), d);
+    for word in ["aa", "ab", "ba", "bab", "", "abba"] {
+        let word: Vec<char> = word.chars().collect();
+        assert_eq!(a.accept(word.iter()), d.accept(word.iter()));
+    }
+}
+
+#[test]
+fn errors() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
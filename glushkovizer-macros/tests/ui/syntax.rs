@@ -0,0 +1,8 @@
+use glushkovizer_macros::{glushkov, glushkov_dfa};
+
+fn main() {
+    let _ = glushkov!("(a+b");
+    let _ = glushkov_dfa!("a.(b+c");
+    let _ = glushkov!(r"a.
+b.'cd'");
+}
@@ -0,0 +1,316 @@
+//! Module for the bit-parallel simulation of homogeneous automata, such as
+//! Glushkov automata, in the manner of Navarro and Raffinot. Each state is a
+//! bit, and reading a symbol amounts to a union of follows, found in tables
+//! indexed by bytes of the set of active states, and an intersection with the
+//! states reached by that symbol. Since every transition entering a state
+//! reads the same symbol, reading a symbol backward amounts to the same
+//! intersection followed by a union of predecessors, which finds in one
+//! backward scan the positions where occurrences start
+
+use super::{r#impl::Inner, Automata, AutomataError, Result, SearchMode};
+use crate::regexp::RegExp;
+use std::{collections::HashMap, hash::Hash, ops::Range};
+
+/// Number of bits in a word of a bitset
+const WORD: usize = u64::BITS as usize;
+/// Number of bits in a chunk indexing the follow tables
+const CHUNK: usize = 8;
+
+#[derive(Debug, Clone)]
+/// Structure simulating a homogeneous automaton with bitsets of states
+pub struct BitGlushkov<T>
+where
+    T: Eq + Hash,
+{
+    /// Number of states
+    states: usize,
+    /// Number of words of a bitset
+    words: usize,
+    /// Bitset of the input states
+    inputs: Vec<u64>,
+    /// Bitset of the output states
+    outputs: Vec<u64>,
+    /// Bitset of the states reached by each symbol
+    masks: HashMap<T, Vec<u64>>,
+    /// Follow tables, the union of the follows of the states of the chunk k
+    /// given by the byte v begins at ((k * 256) + v) * words
+    table: Vec<u64>,
+    /// Predecessor tables, laid out as the follow tables
+    previous: Vec<u64>,
+}
+
+impl<T> BitGlushkov<T>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Creates the simulation of the homogeneous automaton with "states"
+    /// states numbered from 0, and with "transitions" given as
+    /// (from, symbol, to)
+    fn build(
+        states: usize,
+        inputs: impl IntoIterator<Item = usize>,
+        outputs: impl IntoIterator<Item = usize>,
+        transitions: impl IntoIterator<Item = (usize, T, usize)>,
+    ) -> Self {
+        let words = states.div_ceil(WORD).max(1);
+        let mut follows: Vec<Vec<u64>> = vec![vec![0; words]; states];
+        let mut previous: Vec<Vec<u64>> = vec![vec![0; words]; states];
+        let mut masks: HashMap<T, Vec<u64>> = HashMap::new();
+        for (from, symbol, to) in transitions {
+            set(&mut follows[from], to);
+            set(&mut previous[to], from);
+            set(masks.entry(symbol).or_insert_with(|| vec![0; words]), to);
+        }
+        let mut res = Self {
+            states,
+            words,
+            inputs: vec![0; words],
+            outputs: vec![0; words],
+            masks,
+            table: tables(&follows, words),
+            previous: tables(&previous, words),
+        };
+        inputs.into_iter().for_each(|i| set(&mut res.inputs, i));
+        outputs.into_iter().for_each(|i| set(&mut res.outputs, i));
+        res
+    }
+
+    /// Returns the number of states, i.e. of bits of the simulation
+    pub fn states_count(&self) -> usize {
+        self.states
+    }
+
+    /// Writes in "next" the union of the rows of "table" of the states of
+    /// "current"
+    fn union(&self, table: &[u64], current: &[u64], next: &mut [u64]) {
+        next.fill(0);
+        for (k, byte) in current
+            .iter()
+            .flat_map(|w| w.to_le_bytes())
+            .enumerate()
+            .take(self.states.div_ceil(CHUNK))
+        {
+            if byte != 0 {
+                let row = ((k * 256) + byte as usize) * self.words;
+                next.iter_mut()
+                    .zip(&table[row..row + self.words])
+                    .for_each(|(w, f)| *w |= f);
+            }
+        }
+    }
+
+    /// Writes in "next" the states reached from "current" by "symbol", and
+    /// returns ```false``` if there is none
+    fn step(&self, current: &[u64], symbol: &T, next: &mut [u64]) -> bool {
+        let mask = match self.masks.get(symbol) {
+            None => {
+                next.fill(0);
+                return false;
+            }
+            Some(mask) => mask,
+        };
+        self.union(&self.table, current, next);
+        let mut alive = false;
+        next.iter_mut().zip(mask).for_each(|(w, m)| {
+            *w &= m;
+            alive |= *w != 0;
+        });
+        alive
+    }
+
+    /// Returns ```true``` if one of the states of "current" is an output
+    fn is_accepting(&self, current: &[u64]) -> bool {
+        current.iter().zip(&self.outputs).any(|(c, o)| c & o != 0)
+    }
+
+    /// Returns ```true``` if the word is recognized by the automaton and
+    /// ```false``` otherwise
+    pub fn accept<'b>(&self, word: impl IntoIterator<Item = &'b T>) -> bool
+    where
+        T: 'b,
+    {
+        let mut current = self.inputs.clone();
+        let mut next = vec![0; self.words];
+        for symbol in word {
+            if !self.step(&current, symbol, &mut next) {
+                return false;
+            }
+            std::mem::swap(&mut current, &mut next);
+        }
+        self.is_accepting(&current)
+    }
+
+    /// Returns, for each position of "text", whether an occurrence starts
+    /// there, with one backward scan where the outputs are active at every
+    /// position
+    fn starts(&self, text: &[T]) -> Vec<bool> {
+        let mut res = vec![false; text.len() + 1];
+        let mut current = self.outputs.clone();
+        let mut next = vec![0; self.words];
+        for i in (0..=text.len()).rev() {
+            res[i] = current.iter().zip(&self.inputs).any(|(c, s)| c & s != 0);
+            if i == 0 {
+                break;
+            }
+            match self.masks.get(&text[i - 1]) {
+                None => current.fill(0),
+                Some(mask) => {
+                    current.iter_mut().zip(mask).for_each(|(w, m)| *w &= m);
+                    self.union(&self.previous, &current, &mut next);
+                    std::mem::swap(&mut current, &mut next);
+                }
+            }
+            current
+                .iter_mut()
+                .zip(&self.outputs)
+                .for_each(|(w, o)| *w |= o);
+        }
+        res
+    }
+
+    /// Returns the ends of the occurrences starting at "start" in "text", in
+    /// increasing order
+    fn ends_from(&self, text: &[T], start: usize) -> Vec<usize> {
+        let mut res = Vec::new();
+        let mut current = self.inputs.clone();
+        let mut next = vec![0; self.words];
+        if self.is_accepting(&current) {
+            res.push(start);
+        }
+        for (i, symbol) in text.iter().enumerate().skip(start) {
+            if !self.step(&current, symbol, &mut next) {
+                break;
+            }
+            std::mem::swap(&mut current, &mut next);
+            if self.is_accepting(&current) {
+                res.push(i + 1);
+            }
+        }
+        res
+    }
+
+    /// Returns the ranges of the occurrences of the language of the automaton
+    /// in "text", according to "mode", sorted by start then by end. The empty
+    /// word is reported as an empty range when it is recognized. The starts of
+    /// the occurrences are found by one backward scan, then the text is only
+    /// scanned forward from them
+    pub fn find_all(&self, text: &[T], mode: SearchMode) -> Vec<Range<usize>> {
+        let starts = self.starts(text);
+        let mut res = Vec::new();
+        let mut start = 0;
+        while let Some(s) = (start..=text.len()).find(|&i| starts[i]) {
+            let ends = self.ends_from(text, s);
+            match mode {
+                SearchMode::All => {
+                    res.extend(ends.into_iter().map(|end| s..end));
+                    start = s + 1;
+                }
+                SearchMode::LeftmostLongest => {
+                    let end = ends.last().copied().unwrap_or(s);
+                    res.push(s..end);
+                    start = end.max(s + 1);
+                }
+            }
+        }
+        res
+    }
+
+    /// Returns the leftmost longest occurrence of the language of the
+    /// automaton in "text", or ```None``` if there is none
+    pub fn find(&self, text: &[T]) -> Option<Range<usize>> {
+        let start = self.starts(text).iter().position(|&s| s)?;
+        let end = self.ends_from(text, start).last().copied().unwrap_or(start);
+        Some(start..end)
+    }
+}
+
+impl<T> From<&RegExp<T>> for BitGlushkov<T>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Creates the simulation of the Glushkov automaton of the regular
+    /// expression, where the bit 0 is the input state and the bit i is the
+    /// position i
+    fn from(reg: &RegExp<T>) -> Self {
+        let (a, end) = reg.linearization_start(1);
+        let info = a.get_flnf();
+        let outputs: Vec<usize> = info
+            .lasts
+            .iter()
+            .map(|p| p.1)
+            .chain(info.null.then_some(0))
+            .collect();
+        let transitions: Vec<(usize, T, usize)> = info
+            .firsts
+            .iter()
+            .map(|p| (0, p.0.clone(), p.1))
+            .chain(
+                info.follows
+                    .iter()
+                    .flat_map(|(from, set)| set.iter().map(move |to| (from.1, to.0.clone(), to.1))),
+            )
+            .collect();
+        BitGlushkov::build(end, [0], outputs, transitions)
+    }
+}
+
+impl<'a, T, V> TryFrom<&Automata<'a, T, V>> for BitGlushkov<T>
+where
+    T: Eq + Hash + Clone,
+    V: Eq + Clone,
+{
+    type Error = AutomataError;
+
+    /// Creates the simulation of the automaton, returns an error if it is not
+    /// homogeneous
+    fn try_from(automata: &Automata<'a, T, V>) -> Result<Self> {
+        let inner = automata.inner();
+        if !inner.is_homogeneous() {
+            return Err(AutomataError::NotHomogeneous);
+        }
+        let index: HashMap<_, usize> = inner.states().enumerate().map(|(i, rs)| (rs, i)).collect();
+        let transitions: Vec<(usize, T, usize)> = inner
+            .states()
+            .flat_map(|rs| {
+                let from = index[rs];
+                let index = &index;
+                rs.as_ref().get_follows().flat_map(move |(symbol, set)| {
+                    set.iter()
+                        .filter_map(move |to| index.get(to).map(|&to| (from, symbol.clone(), to)))
+                })
+            })
+            .collect();
+        Ok(BitGlushkov::build(
+            index.len(),
+            inner.inputs().map(|rs| index[rs]),
+            inner.outputs().map(|rs| index[rs]),
+            transitions,
+        ))
+    }
+}
+
+/// Returns the tables indexed by bytes of sets of states, whose rows are the
+/// unions of "rows" of the states of the set
+fn tables(rows: &[Vec<u64>], words: usize) -> Vec<u64> {
+    let chunks = rows.len().div_ceil(CHUNK);
+    let mut table = vec![0; chunks * 256 * words];
+    for k in 0..chunks {
+        for v in 1..256 {
+            let row = ((k * 256) + v) * words;
+            for bit in (0..CHUNK).filter(|bit| v & (1 << bit) != 0) {
+                if let Some(r) = rows.get(k * CHUNK + bit) {
+                    table[row..row + words]
+                        .iter_mut()
+                        .zip(r)
+                        .for_each(|(w, f)| *w |= f);
+                }
+            }
+        }
+    }
+    table
+}
+
+/// Adds "bit" to the bitset "set"
+fn set(set: &mut [u64], bit: usize) {
+    set[bit / WORD] |= 1 << (bit % WORD);
+}
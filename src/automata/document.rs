@@ -0,0 +1,139 @@
+//! Module for saving an automaton together with its alphabet and metadata.
+//! A document is serialized as its automaton with additional fields, so an
+//! automaton can be read from a document and the reverse
+
+use super::{r#impl::Inner, Automata};
+use crate::regexp::RegExp;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+/// Metadata of a saved automaton
+pub struct Metadata<V> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Name of the automaton
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Regular expression from which the automaton was built
+    pub regex: Option<String>,
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    /// Labels of states, as pairs of a state and its label
+    pub labels: Vec<(V, String)>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(
+    try_from = "RawDocument<'a, T, V>",
+    bound(
+        serialize = "T: Serialize, V: Serialize",
+        deserialize = "T: Deserialize<'de> + Debug + 'a, V: Deserialize<'de> + Debug + 'a"
+    )
+)]
+/// Structure representing an automaton saved with its alphabet and metadata
+pub struct Document<'a, T, V>
+where
+    T: Eq + Hash + Clone,
+    V: Eq + Clone,
+{
+    #[serde(flatten)]
+    /// Saved automaton
+    pub automata: Automata<'a, T, V>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Alphabet of the automaton, which may contain symbols without
+    /// transition. If it is given, every transition must read one of its
+    /// symbols
+    pub alphabet: Option<Vec<T>>,
+    #[serde(flatten)]
+    /// Metadata of the automaton
+    pub metadata: Metadata<V>,
+}
+
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de> + Debug + 'a, \
+    V: Deserialize<'de> + Debug + 'a"))]
+/// Unchecked fields of a [Document], used for deserialization
+struct RawDocument<'a, T, V>
+where
+    T: Eq + Hash + Clone,
+    V: Eq + Clone,
+{
+    #[serde(flatten)]
+    automata: Automata<'a, T, V>,
+    #[serde(default)]
+    alphabet: Option<Vec<T>>,
+    #[serde(flatten)]
+    metadata: Metadata<V>,
+}
+
+impl<'a, T, V> TryFrom<RawDocument<'a, T, V>> for Document<'a, T, V>
+where
+    T: Eq + Hash + Clone + Debug,
+    V: Eq + Clone + Debug,
+{
+    type Error = String;
+
+    fn try_from(raw: RawDocument<'a, T, V>) -> Result<Self, Self::Error> {
+        let inner = raw.automata.inner();
+        if let Some(alphabet) = &raw.alphabet {
+            let symbol = inner
+                .states()
+                .flat_map(|rs| rs.as_ref().get_follows().map(|(symbol, _)| symbol))
+                .find(|symbol| !alphabet.contains(symbol));
+            if let Some(symbol) = symbol {
+                return Err(format!("symbol {:?} not in the alphabet", symbol));
+            }
+        }
+        let labelled = raw
+            .metadata
+            .labels
+            .iter()
+            .find(|(state, _)| !inner.states().any(|rs| rs.as_ref().get_value() == state));
+        if let Some((state, label)) = labelled {
+            return Err(format!(
+                "label {:?} of the state {:?} not in states",
+                label, state
+            ));
+        }
+        Ok(Document {
+            automata: raw.automata,
+            alphabet: raw.alphabet,
+            metadata: raw.metadata,
+        })
+    }
+}
+
+impl<'a, T, V> From<Automata<'a, T, V>> for Document<'a, T, V>
+where
+    T: Eq + Hash + Clone,
+    V: Eq + Clone,
+{
+    /// Creates a document of "automata" without alphabet nor metadata
+    fn from(automata: Automata<'a, T, V>) -> Self {
+        Document {
+            automata,
+            alphabet: None,
+            metadata: Metadata {
+                name: None,
+                regex: None,
+                labels: Vec::new(),
+            },
+        }
+    }
+}
+
+impl<'a, T> From<RegExp<T>> for Document<'a, T, usize>
+where
+    T: Eq + Hash + Clone + Display,
+{
+    /// Creates a document of the Glushkov automaton of "reg", whose metadata
+    /// keep "reg" as source regular expression
+    fn from(reg: RegExp<T>) -> Self {
+        let regex = reg.to_string();
+        let mut document = Document::from(Automata::from(reg));
+        document.metadata.regex = Some(regex);
+        document
+    }
+}
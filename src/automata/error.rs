@@ -1,6 +1,8 @@
 //! Module designed to facilitate the management of errors that may occur
 //! during automata operation
 
+use super::formats::FormatError;
+
 #[derive(thiserror::Error, Debug)]
 /// Enumeration of possible errors when manipulating automata
 pub enum AutomataError {
@@ -26,6 +28,21 @@ pub enum AutomataError {
     #[error("The output state is not in the states")]
     /// Error representing the fact that the output state is not in the states
     OutputStateIsNotInStates,
+    #[error("The automaton is not homogeneous")]
+    /// Error representing the fact that the automaton is not homogeneous
+    NotHomogeneous,
+    #[error("Invalid transition table")]
+    /// Error representing the fact that the parts of a transition table are
+    /// inconsistent
+    InvalidTable,
+    #[error("\"{0}\" is not a Rust identifier")]
+    /// Error representing the fact that a name given for generated Rust code
+    /// is not an identifier, or is a keyword
+    InvalidIdentifier(String),
+    #[error(transparent)]
+    /// Error representing the fact that an automaton could not be read from
+    /// another format
+    Format(#[from] FormatError),
 }
 
 /// Result alias type for automaton manipulation functions
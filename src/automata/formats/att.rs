@@ -0,0 +1,142 @@
+//! Module for reading and writing acceptors in the AT&T FSM text format, as
+//! used by OpenFst. Each line is either a transition
+//! ```source target symbol [weight]``` or a final state
+//! ```state [weight]```, and the initial state is the first state of the
+//! file. Weights are only used to drop what has an infinite weight, and the
+//! empty word ```<eps>``` is refused
+
+use super::FormatError;
+use crate::automata::{r#impl::Inner, AddStates, Automata, InOut, MutTransition, Result};
+use std::{fmt::Display, hash::Hash};
+
+/// Symbol of the empty word in OpenFst symbol tables
+const EPSILON: &str = "<eps>";
+
+/// Returns the automaton described by the AT&T acceptor "input", whose states
+/// are the numbers of the file and whose symbols are its labels
+pub fn from_att<'a>(input: &str) -> Result<Automata<'a, String, usize>> {
+    let a = Automata::new();
+    let mut initial = true;
+    for (l, line) in input.lines().enumerate() {
+        let fields: Vec<(usize, &str)> = line
+            .split_whitespace()
+            .map(|f| (f.as_ptr() as usize - line.as_ptr() as usize, f))
+            .collect();
+        let error = |field: usize, message: String| FormatError::Syntax {
+            line: l + 1,
+            column: line[..fields[field].0].chars().count() + 1,
+            message,
+        };
+        let state = |field: usize| -> std::result::Result<usize, FormatError> {
+            let (_, s) = fields[field];
+            s.parse()
+                .map_err(|_| error(field, format!("the state '{}' is not a number", s)))
+        };
+        let weight = |field: usize| -> std::result::Result<bool, FormatError> {
+            match fields.get(field) {
+                None => Ok(true),
+                Some((_, w)) => w
+                    .parse::<f64>()
+                    .map(|w| w.is_finite())
+                    .map_err(|_| error(field, format!("the weight '{}' is not a number", w))),
+            }
+        };
+        let (from, transition) = match fields.len() {
+            0 => continue,
+            1 | 2 => (state(0)?, None),
+            3 | 4 => (state(0)?, Some((state(1)?, fields[2].1))),
+            _ => {
+                return Err(error(
+                    4,
+                    String::from("transducers are not supported, expected an acceptor"),
+                )
+                .into())
+            }
+        };
+        a.add_state(from);
+        if initial {
+            a.add_input(&from)?;
+            initial = false;
+        }
+        match transition {
+            None if weight(1)? => {
+                a.add_output(&from)?;
+            }
+            None => {}
+            Some((_, EPSILON)) => {
+                return Err(error(2, String::from("the empty word is not supported")).into())
+            }
+            Some((to, symbol)) => {
+                a.add_state(to);
+                if weight(3)? {
+                    a.add_transition(&from, &to, String::from(symbol))?;
+                }
+            }
+        }
+    }
+    Ok(a)
+}
+
+/// Returns the AT&T acceptor of "automata", whose initial state must be
+/// unique. Symbols must not be empty nor contain whitespaces, and the
+/// acceptor is empty if the initial state has no transition and is not final,
+/// since the format could not tell which state is initial
+pub fn to_att<'a, T>(automata: &Automata<'a, T, usize>) -> Result<String>
+where
+    T: Eq + Hash + Clone + Display,
+{
+    let inner = automata.inner();
+    let initial = match inner.inputs_count() {
+        0 => return Ok(String::new()),
+        1 => inner.inputs().next(),
+        _ => {
+            return Err(FormatError::Invalid(String::from(
+                "the AT&T format has a single initial state",
+            ))
+            .into())
+        }
+    };
+    let mut edges: Vec<(bool, String)> = Vec::new();
+    for (from, symbols, to) in inner.edges(false) {
+        for symbol in symbols {
+            let symbol = symbol.to_string();
+            if symbol.is_empty() || symbol.contains(char::is_whitespace) {
+                return Err(FormatError::Invalid(format!(
+                    "the symbol '{}' cannot be written in the AT&T format",
+                    symbol
+                ))
+                .into());
+            }
+            edges.push((
+                Some(from) == initial,
+                format!(
+                    "{}\t{}\t{}\n",
+                    from.as_ref().get_value(),
+                    to.as_ref().get_value(),
+                    symbol
+                ),
+            ));
+        }
+    }
+    let mut finals: Vec<(bool, String)> = inner
+        .outputs()
+        .map(|rs| {
+            (
+                Some(rs) == initial,
+                format!("{}\n", rs.as_ref().get_value()),
+            )
+        })
+        .collect();
+    let first = |lines: &mut Vec<(bool, String)>| lines.sort_by_key(|(initial, _)| !initial);
+    let lines = if edges.iter().any(|(initial, _)| *initial) {
+        first(&mut edges);
+        [edges, finals].concat()
+    } else if finals.iter().any(|(initial, _)| *initial) {
+        first(&mut finals);
+        let initial = finals.remove(0);
+        [vec![initial], edges, finals].concat()
+    } else {
+        Vec::new()
+    };
+    Ok(lines.into_iter().map(|(_, line)| line).collect())
+}
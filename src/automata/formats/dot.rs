@@ -0,0 +1,465 @@
+//! Module for reading an automaton from a Graphviz digraph. The subset of DOT
+//! emitted by this crate is read back, as well as the usual hand-written
+//! conventions:
+//! - the value of a state is its ```label```, or its name if it has none;
+//! - a state is final if it has ```peripheries=2``` or
+//!   ```shape=doublecircle```;
+//! - a state is initial if it has ```shape=diamond``` or if it is the target
+//!   of an edge from an invisible start node, i.e. a node with
+//!   ```shape=point```, ```shape=none```, ```shape=plaintext``` or
+//!   ```style=invis```;
+//! - the symbols of a transition are given by the ```label``` of its edge,
+//!   separated by commas. A label is always split, so a symbol containing a
+//!   comma, or surrounded by whitespace, cannot be read back, even from an
+//!   edge written without ```merge_edges```.
+//!
+//! Subgraphs and clusters are flattened and the attributes that have no
+//! meaning for an automaton, such as colors, are ignored.
+
+use super::FormatError;
+use crate::automata::{AddStates, Automata, InOut, MutTransition, Result};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+/// Attributes of a node or an edge
+type Attributes = HashMap<String, String>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Token of the DOT language
+enum Token {
+    /// Identifier, numeral, quoted or HTML string, with whether it was quoted
+    Id(String, bool),
+    /// Directed edge operator
+    Arrow,
+    /// Undirected edge operator
+    Line,
+    /// Punctuation character
+    Punct(char),
+}
+
+/// Token with its line and column
+type Located = (Token, usize, usize);
+
+/// Returns the tokens of "input", without comments
+fn tokenize(input: &str) -> std::result::Result<Vec<Located>, FormatError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let (mut i, mut line, mut column) = (0, 1, 1);
+    let mut line_start = true;
+    while i < chars.len() {
+        let (c, start) = (chars[i], (line, column));
+        let error = |message: &str| FormatError::Syntax {
+            line: start.0,
+            column: start.1,
+            message: String::from(message),
+        };
+        let next = chars.get(i + 1).copied();
+        let len = if c == '\n' {
+            line += 1;
+            column = 1;
+            line_start = true;
+            i += 1;
+            continue;
+        } else if c.is_whitespace() {
+            1
+        } else if (c == '#' && line_start) || (c == '/' && next == Some('/')) {
+            chars[i..].iter().take_while(|c| **c != '\n').count()
+        } else if c == '/' && next == Some('*') {
+            match (i + 2..chars.len()).find(|j| chars[*j] == '*' && chars.get(j + 1) == Some(&'/'))
+            {
+                None => return Err(error("unterminated comment")),
+                Some(j) => j + 2 - i,
+            }
+        } else if c == '"' {
+            let mut s = String::new();
+            let mut j = i + 1;
+            loop {
+                match (chars.get(j), chars.get(j + 1)) {
+                    (None, _) => return Err(error("unterminated string")),
+                    (Some('"'), _) => break,
+                    (Some('\\'), Some('"')) => s.push('"'),
+                    (Some('\\'), Some('\\')) => s.push('\\'),
+                    (Some('\\'), Some('\n')) => {}
+                    (Some('\\'), Some(c)) => {
+                        s.push('\\');
+                        s.push(*c);
+                    }
+                    (Some(c), _) => {
+                        s.push(*c);
+                        j += 1;
+                        continue;
+                    }
+                }
+                j += 2;
+            }
+            tokens.push((Token::Id(s, true), start.0, start.1));
+            j + 1 - i
+        } else if c == '<' {
+            let mut depth = 0;
+            match (i..chars.len()).find(|j| {
+                match chars[*j] {
+                    '<' => depth += 1,
+                    '>' => depth -= 1,
+                    _ => {}
+                }
+                depth == 0
+            }) {
+                None => return Err(error("unterminated HTML string")),
+                Some(j) => {
+                    let s: String = chars[i + 1..j].iter().collect();
+                    tokens.push((Token::Id(s, true), start.0, start.1));
+                    j + 1 - i
+                }
+            }
+        } else if c == '-' && next == Some('>') {
+            tokens.push((Token::Arrow, start.0, start.1));
+            2
+        } else if c == '-' && next == Some('-') {
+            tokens.push((Token::Line, start.0, start.1));
+            2
+        } else if "{}[];,=:+".contains(c) {
+            tokens.push((Token::Punct(c), start.0, start.1));
+            1
+        } else if is_id_char(c)
+            || (c == '-' && next.is_some_and(|n| n.is_ascii_digit() || n == '.'))
+        {
+            let len = 1 + chars[i + 1..]
+                .iter()
+                .take_while(|c| is_id_char(**c))
+                .count();
+            tokens.push((
+                Token::Id(chars[i..i + len].iter().collect(), false),
+                start.0,
+                start.1,
+            ));
+            len
+        } else {
+            return Err(error(&format!("unexpected character '{}'", c)));
+        };
+        let skipped = &chars[i..i + len];
+        match skipped.iter().filter(|c| **c == '\n').count() {
+            0 => column += len,
+            newlines => {
+                line += newlines;
+                column = skipped.iter().rev().take_while(|c| **c != '\n').count() + 1;
+            }
+        }
+        line_start &= c.is_whitespace();
+        i += len;
+    }
+    Ok(tokens)
+}
+
+/// Returns whether "c" can be part of an unquoted identifier or numeral
+fn is_id_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.' || !c.is_ascii()
+}
+
+/// Default attributes of the nodes and edges of a graph or subgraph
+#[derive(Debug, Clone, Default)]
+struct Scope {
+    node: Attributes,
+    edge: Attributes,
+}
+
+/// Parser of a DOT digraph, gathering its nodes and edges
+struct Parser {
+    tokens: Vec<Located>,
+    pos: usize,
+    end: (usize, usize),
+    nodes: IndexMap<String, Attributes>,
+    edges: Vec<(String, String, Attributes)>,
+}
+
+impl Parser {
+    /// Returns the current token, if any
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _, _)| t)
+    }
+
+    /// Returns whether the current token is the unquoted keyword "keyword",
+    /// case insensitive
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Id(s, false)) if s.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Returns a syntax error on the current token
+    fn error(&self, message: &str) -> FormatError {
+        let (line, column) = self
+            .tokens
+            .get(self.pos)
+            .map_or(self.end, |(_, l, c)| (*l, *c));
+        FormatError::Syntax {
+            line,
+            column,
+            message: String::from(message),
+        }
+    }
+
+    /// Consumes the current token if it is "token"
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.peek() == Some(token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    /// Consumes the current token, which must be "token"
+    fn expect(&mut self, token: &Token, what: &str) -> std::result::Result<(), FormatError> {
+        match self.eat(token) {
+            true => Ok(()),
+            false => Err(self.error(&format!("expected {}", what))),
+        }
+    }
+
+    /// Consumes and returns an identifier, concatenating quoted strings joined
+    /// by '+'
+    fn id(&mut self) -> std::result::Result<String, FormatError> {
+        match self.peek().cloned() {
+            Some(Token::Id(mut s, quoted)) => {
+                self.pos += 1;
+                while quoted
+                    && self.peek() == Some(&Token::Punct('+'))
+                    && matches!(
+                        self.tokens.get(self.pos + 1),
+                        Some((Token::Id(_, true), _, _))
+                    )
+                {
+                    self.pos += 1;
+                    s.push_str(&self.id()?);
+                }
+                Ok(s)
+            }
+            _ => Err(self.error("expected an identifier")),
+        }
+    }
+
+    /// Parses the whole digraph
+    fn graph(&mut self) -> std::result::Result<(), FormatError> {
+        if self.is_keyword("strict") {
+            self.pos += 1;
+        }
+        if self.is_keyword("graph") {
+            return Err(self.error("only directed graphs are supported"));
+        }
+        if !self.is_keyword("digraph") {
+            return Err(self.error("expected 'digraph'"));
+        }
+        self.pos += 1;
+        if matches!(self.peek(), Some(Token::Id(_, _))) {
+            self.id()?;
+        }
+        self.expect(&Token::Punct('{'), "'{'")?;
+        self.statements(&mut Scope::default())?;
+        self.expect(&Token::Punct('}'), "'}'")?;
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error("expected the end of the input")),
+        }
+    }
+
+    /// Parses statements until a closing brace, returning the nodes they
+    /// mention
+    fn statements(&mut self, scope: &mut Scope) -> std::result::Result<Vec<String>, FormatError> {
+        let mut mentioned = Vec::new();
+        while !matches!(self.peek(), Some(Token::Punct('}')) | None) {
+            mentioned.extend(self.statement(scope)?);
+            self.eat(&Token::Punct(';'));
+        }
+        Ok(mentioned)
+    }
+
+    /// Parses a statement, returning the nodes it mentions
+    fn statement(&mut self, scope: &mut Scope) -> std::result::Result<Vec<String>, FormatError> {
+        let assignment = self.tokens.get(self.pos + 1).map(|t| &t.0) == Some(&Token::Punct('='));
+        if let (Some(Token::Id(keyword, false)), false) = (self.peek(), assignment) {
+            let keyword = keyword.to_ascii_lowercase();
+            if matches!(keyword.as_str(), "graph" | "node" | "edge") {
+                self.pos += 1;
+                let attributes = self.attributes()?;
+                match keyword.as_str() {
+                    "node" => scope.node.extend(attributes),
+                    "edge" => scope.edge.extend(attributes),
+                    _ => {}
+                }
+                return Ok(Vec::new());
+            }
+        }
+        if assignment {
+            self.id()?;
+            self.pos += 1;
+            self.id()?;
+            return Ok(Vec::new());
+        }
+        let mut operands = vec![self.operand(scope)?];
+        while self.peek() == Some(&Token::Arrow) || self.peek() == Some(&Token::Line) {
+            if self.eat(&Token::Line) {
+                self.pos -= 1;
+                return Err(self.error("only directed edges are supported"));
+            }
+            self.pos += 1;
+            operands.push(self.operand(scope)?);
+        }
+        let attributes = self.attributes()?;
+        if operands.len() == 1 {
+            operands[0].iter().for_each(|id| {
+                if let Some(node) = self.nodes.get_mut(id) {
+                    node.extend(attributes.clone())
+                }
+            });
+        } else {
+            let mut edge = scope.edge.clone();
+            edge.extend(attributes);
+            operands.windows(2).for_each(|pair| {
+                pair[0].iter().for_each(|from| {
+                    pair[1]
+                        .iter()
+                        .for_each(|to| self.edges.push((from.clone(), to.clone(), edge.clone())))
+                })
+            });
+        }
+        Ok(operands.concat())
+    }
+
+    /// Parses a node, with an optional port, or a subgraph, returning the
+    /// nodes it is made of
+    fn operand(&mut self, scope: &Scope) -> std::result::Result<Vec<String>, FormatError> {
+        if self.is_keyword("subgraph") || self.peek() == Some(&Token::Punct('{')) {
+            if self.is_keyword("subgraph") {
+                self.pos += 1;
+                if matches!(self.peek(), Some(Token::Id(_, _))) {
+                    self.id()?;
+                }
+            }
+            self.expect(&Token::Punct('{'), "'{'")?;
+            let nodes = self.statements(&mut scope.clone())?;
+            self.expect(&Token::Punct('}'), "'}'")?;
+            return Ok(nodes);
+        }
+        let id = self.id()?;
+        while self.eat(&Token::Punct(':')) {
+            self.id()?;
+        }
+        self.nodes
+            .entry(id.clone())
+            .or_insert_with(|| scope.node.clone());
+        Ok(vec![id])
+    }
+
+    /// Parses a possibly empty sequence of attribute lists
+    fn attributes(&mut self) -> std::result::Result<Attributes, FormatError> {
+        let mut attributes = Attributes::new();
+        while self.eat(&Token::Punct('[')) {
+            while !self.eat(&Token::Punct(']')) {
+                let key = self.id()?;
+                self.expect(&Token::Punct('='), "'='")?;
+                let value = self.id()?;
+                attributes.insert(key, value);
+                if !self.eat(&Token::Punct(',')) {
+                    self.eat(&Token::Punct(';'));
+                }
+            }
+        }
+        Ok(attributes)
+    }
+}
+
+/// Returns whether the node of "attributes" is an invisible start node
+fn is_start(attributes: &Attributes) -> bool {
+    matches!(
+        attributes.get("shape").map(String::as_str),
+        Some("point" | "none" | "plaintext" | "plain")
+    ) || attributes
+        .get("style")
+        .is_some_and(|style| style.contains("invis"))
+}
+
+/// Returns whether the node of "attributes" is a final state
+fn is_final(attributes: &Attributes) -> bool {
+    attributes
+        .get("peripheries")
+        .and_then(|p| p.parse::<usize>().ok())
+        .is_some_and(|p| p >= 2)
+        || matches!(
+            attributes.get("shape").map(String::as_str),
+            Some("doublecircle" | "doubleoctagon" | "tripleoctagon")
+        )
+}
+
+/// Returns the automaton described by the DOT digraph "input", whose states
+/// are the values of the nodes and whose symbols are the labels of the edges,
+/// split on commas and trimmed. The automata whose symbols contain a comma are
+/// therefore not read back as they were written
+pub fn from_dot<'a>(input: &str) -> Result<Automata<'a, String, String>> {
+    let tokens = tokenize(input)?;
+    let end = input
+        .lines()
+        .enumerate()
+        .last()
+        .map_or((1, 1), |(l, s)| (l + 1, s.chars().count() + 1));
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        end,
+        nodes: IndexMap::new(),
+        edges: Vec::new(),
+    };
+    parser.graph()?;
+    let a = Automata::new();
+    let mut values: HashMap<&String, String> = HashMap::new();
+    for (id, attributes) in parser.nodes.iter().filter(|(_, attr)| !is_start(attr)) {
+        let value = attributes
+            .get("label")
+            .map_or_else(|| id.clone(), |l| l.replace("\\N", id));
+        if !a.add_state(value.clone()) {
+            return Err(
+                FormatError::Invalid(format!("two states have the value \"{}\"", value)).into(),
+            );
+        }
+        if attributes.get("shape").map(String::as_str) == Some("diamond") {
+            a.add_input(&value)?;
+        }
+        if is_final(attributes) {
+            a.add_output(&value)?;
+        }
+        values.insert(id, value);
+    }
+    for (from, to, attributes) in parser.edges.iter() {
+        let (from, to) = match (values.get(from), values.get(to)) {
+            (None, None) => continue,
+            (None, Some(to)) => {
+                a.add_input(to)?;
+                continue;
+            }
+            (Some(from), None) => {
+                return Err(FormatError::Invalid(format!(
+                    "the state \"{}\" goes to the start node \"{}\"",
+                    from, to
+                ))
+                .into())
+            }
+            (Some(from), Some(to)) => (from, to),
+        };
+        let symbols: Vec<&str> = attributes
+            .get("label")
+            .map(|l| {
+                l.split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if symbols.is_empty() {
+            return Err(FormatError::Invalid(format!(
+                "the transition from \"{}\" to \"{}\" has no label",
+                from, to
+            ))
+            .into());
+        }
+        for symbol in symbols {
+            a.add_transition(from, to, String::from(symbol))?;
+        }
+    }
+    Ok(a)
+}
@@ -0,0 +1,405 @@
+//! Module for reading and writing finite automata in a subset of the Hanoi
+//! Omega-Automata format. The atomic propositions are the symbols of the
+//! automaton, and a symbol is read as the valuation where only its
+//! proposition holds: the label ```[0&!1&!2]``` reads the first of three
+//! symbols, and a label made of conjunctions and disjunctions of literals
+//! reads every symbol whose valuation satisfies it. The final states are the
+//! states of the acceptance set ```0```. This interpretation is not standard:
+//! HOA describes automata over infinite words, so other tools read the written
+//! files as Büchi automata over valuations. Alternation and transition-based
+//! acceptance are refused
+
+use super::FormatError;
+use crate::automata::{r#impl::Inner, AddStates, Automata, InOut, MutTransition, Result};
+use std::{
+    fmt::{Display, Write},
+    hash::Hash,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Token of the HOA format
+enum Token {
+    /// Name of a header item, without its colon
+    Header(String),
+    /// Identifier
+    Id(String),
+    /// Quoted string
+    Str(String),
+    /// Integer
+    Int(usize),
+    /// Separator of the header and the body, or end of the body
+    Mark(String),
+    /// Punctuation character
+    Punct(char),
+}
+
+/// Token with its line and column
+type Located = (Token, usize, usize);
+
+/// Returns the tokens of "input", without comments
+fn tokenize(input: &str) -> std::result::Result<Vec<Located>, FormatError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let (mut i, mut line, mut column) = (0, 1, 1);
+    while i < chars.len() {
+        let c = chars[i];
+        let error = |message: String| FormatError::Syntax {
+            line,
+            column,
+            message,
+        };
+        let take = |from: usize, f: fn(char) -> bool| {
+            from + chars[from..].iter().take_while(|c| f(**c)).count()
+        };
+        let (token, end) = if c.is_whitespace() {
+            (None, i + 1)
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            match (i + 2..chars.len()).find(|j| chars[*j] == '*' && chars.get(j + 1) == Some(&'/'))
+            {
+                None => return Err(error(String::from("unterminated comment"))),
+                Some(j) => (None, j + 2),
+            }
+        } else if c == '"' {
+            let mut s = String::new();
+            let mut j = i + 1;
+            loop {
+                match chars.get(j) {
+                    None => return Err(error(String::from("unterminated string"))),
+                    Some('"') => break,
+                    Some('\\') if j + 1 < chars.len() => {
+                        s.push(chars[j + 1]);
+                        j += 2;
+                    }
+                    Some(c) => {
+                        s.push(*c);
+                        j += 1;
+                    }
+                }
+            }
+            (Some(Token::Str(s)), j + 1)
+        } else if c.is_ascii_digit() {
+            let end = take(i, |c| c.is_ascii_digit());
+            let s: String = chars[i..end].iter().collect();
+            match s.parse() {
+                Ok(n) => (Some(Token::Int(n)), end),
+                Err(_) => return Err(error(format!("the integer {} is too large", s))),
+            }
+        } else if c == '-' && chars.get(i + 1) == Some(&'-') {
+            let end = take(i + 2, |c| c.is_ascii_alphabetic());
+            let s: String = chars[i..end].iter().collect();
+            if chars.get(end..end + 2) != Some(&['-', '-']) {
+                return Err(error(format!("unexpected '{}'", s)));
+            }
+            (Some(Token::Mark(s[2..].to_string())), end + 2)
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let end = take(i, |c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+            let s: String = chars[i..end].iter().collect();
+            match chars.get(end) {
+                Some(':') => (Some(Token::Header(s)), end + 1),
+                _ => (Some(Token::Id(s)), end),
+            }
+        } else if "[]{}!&|()".contains(c) {
+            (Some(Token::Punct(c)), i + 1)
+        } else {
+            return Err(error(format!("unexpected character '{}'", c)));
+        };
+        if let Some(token) = token {
+            tokens.push((token, line, column));
+        }
+        chars[i..end].iter().for_each(|c| match c {
+            '\n' => {
+                line += 1;
+                column = 1;
+            }
+            _ => column += 1,
+        });
+        i = end;
+    }
+    Ok(tokens)
+}
+
+/// Parser of the tokens of an automaton
+struct Parser {
+    tokens: Vec<Located>,
+    pos: usize,
+    end: (usize, usize),
+}
+
+impl Parser {
+    /// Returns the current token, if any
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _, _)| t)
+    }
+
+    /// Returns a syntax error on the current token
+    fn error(&self, message: &str) -> FormatError {
+        let (line, column) = self
+            .tokens
+            .get(self.pos)
+            .map_or(self.end, |(_, l, c)| (*l, *c));
+        FormatError::Syntax {
+            line,
+            column,
+            message: String::from(message),
+        }
+    }
+
+    /// Consumes the current token, which must be "token"
+    fn expect(&mut self, token: Token, what: &str) -> std::result::Result<(), FormatError> {
+        match self.peek() == Some(&token) {
+            true => {
+                self.pos += 1;
+                Ok(())
+            }
+            false => Err(self.error(&format!("expected {}", what))),
+        }
+    }
+
+    /// Consumes and returns an integer
+    fn int(&mut self) -> std::result::Result<usize, FormatError> {
+        match self.peek() {
+            Some(&Token::Int(n)) => {
+                self.pos += 1;
+                Ok(n)
+            }
+            _ => Err(self.error("expected an integer")),
+        }
+    }
+
+    /// Consumes a label and its closing bracket, returning the symbols among
+    /// "count" whose valuation satisfies it
+    fn label(&mut self, count: usize) -> std::result::Result<Vec<usize>, FormatError> {
+        let mut accepted = vec![false; count];
+        loop {
+            let mut conjunction = vec![true; count];
+            loop {
+                let negated = self.peek() == Some(&Token::Punct('!'));
+                if negated {
+                    self.pos += 1;
+                }
+                match self.peek() {
+                    Some(Token::Id(b)) if b == "t" || b == "f" => {
+                        if (b == "t") == negated {
+                            conjunction.fill(false);
+                        }
+                    }
+                    Some(&Token::Int(i)) if i < count => conjunction
+                        .iter_mut()
+                        .enumerate()
+                        .for_each(|(s, c)| *c &= (s == i) != negated),
+                    Some(Token::Int(_)) => return Err(self.error("unknown atomic proposition")),
+                    Some(Token::Punct('(')) => {
+                        return Err(self.error("parentheses are not supported in labels"))
+                    }
+                    _ => return Err(self.error("expected an atomic proposition, 't' or 'f'")),
+                }
+                self.pos += 1;
+                if self.peek() != Some(&Token::Punct('&')) {
+                    break;
+                }
+                self.pos += 1;
+            }
+            accepted
+                .iter_mut()
+                .zip(conjunction)
+                .for_each(|(a, c)| *a |= c);
+            if self.peek() != Some(&Token::Punct('|')) {
+                break;
+            }
+            self.pos += 1;
+        }
+        self.expect(Token::Punct(']'), "']'")?;
+        Ok((0..count).filter(|s| accepted[*s]).collect())
+    }
+
+    /// Consumes an optional acceptance signature, returning whether it
+    /// contains the set 0
+    fn acceptance(&mut self) -> std::result::Result<bool, FormatError> {
+        let mut accepting = false;
+        if self.peek() == Some(&Token::Punct('{')) {
+            self.pos += 1;
+            while self.peek() != Some(&Token::Punct('}')) {
+                accepting |= self.int()? == 0;
+            }
+            self.pos += 1;
+        }
+        Ok(accepting)
+    }
+}
+
+/// Returns the automaton described by the HOA file "input", whose states are
+/// the numbers of the file and whose symbols are its atomic propositions
+pub fn from_hoa<'a>(input: &str) -> Result<Automata<'a, String, usize>> {
+    let tokens = tokenize(input)?;
+    let end = input
+        .lines()
+        .enumerate()
+        .last()
+        .map_or((1, 1), |(l, s)| (l + 1, s.chars().count() + 1));
+    let mut p = Parser {
+        tokens,
+        pos: 0,
+        end,
+    };
+    if p.peek() != Some(&Token::Header(String::from("HOA"))) {
+        return Err(p.error("expected 'HOA:'").into());
+    }
+    let a = Automata::new();
+    let mut symbols: Vec<String> = Vec::new();
+    let mut starts: Vec<usize> = Vec::new();
+    loop {
+        let header = match p.peek() {
+            Some(Token::Header(h)) => h.clone(),
+            Some(Token::Mark(m)) if m == "BODY" => break,
+            _ => return Err(p.error("expected a header item or '--BODY--'").into()),
+        };
+        p.pos += 1;
+        match header.as_str() {
+            "HOA" => match p.peek() {
+                Some(Token::Id(v)) if v == "v1" => p.pos += 1,
+                _ => return Err(p.error("expected the version 'v1'").into()),
+            },
+            "States" => (0..p.int()?).for_each(|s| {
+                a.add_state(s);
+            }),
+            "Start" => {
+                starts.push(p.int()?);
+                if p.peek() == Some(&Token::Punct('&')) {
+                    return Err(p.error("alternating automata are not supported").into());
+                }
+            }
+            "AP" => {
+                let count = p.int()?;
+                for _ in 0..count {
+                    match p.peek() {
+                        Some(Token::Str(s)) => symbols.push(s.clone()),
+                        _ => return Err(p.error("expected an atomic proposition").into()),
+                    }
+                    p.pos += 1;
+                }
+            }
+            _ => {
+                while !matches!(p.peek(), Some(Token::Header(_) | Token::Mark(_)) | None) {
+                    p.pos += 1;
+                }
+            }
+        }
+    }
+    p.pos += 1;
+    let mut state = None;
+    loop {
+        match p.peek().cloned() {
+            Some(Token::Mark(m)) if m == "END" => break,
+            Some(Token::Header(h)) if h == "State" => {
+                p.pos += 1;
+                if p.peek() == Some(&Token::Punct('[')) {
+                    return Err(p.error("state labels are not supported").into());
+                }
+                let s = p.int()?;
+                if let Some(Token::Str(_)) = p.peek() {
+                    p.pos += 1;
+                }
+                a.add_state(s);
+                if p.acceptance()? {
+                    a.add_output(&s)?;
+                }
+                state = Some(s);
+            }
+            Some(Token::Punct('[')) => {
+                let from = state.ok_or_else(|| p.error("expected 'State:'"))?;
+                p.pos += 1;
+                let labels = p.label(symbols.len())?;
+                let to = p.int()?;
+                if p.peek() == Some(&Token::Punct('&')) {
+                    return Err(p.error("alternating automata are not supported").into());
+                }
+                if p.peek() == Some(&Token::Punct('{')) {
+                    return Err(p
+                        .error("transition-based acceptance is not supported")
+                        .into());
+                }
+                a.add_state(to);
+                for i in labels {
+                    a.add_transition(&from, &to, symbols[i].clone())?;
+                }
+            }
+            Some(Token::Mark(m)) if m == "ABORT" => {
+                return Err(p.error("the automaton was aborted").into())
+            }
+            Some(Token::Int(_)) => {
+                return Err(p.error("edges without label are not supported").into())
+            }
+            _ => return Err(p.error("expected 'State:', an edge or '--END--'").into()),
+        }
+    }
+    for s in starts {
+        a.add_input(&s)
+            .map_err(|_| FormatError::Invalid(format!("the initial state {} is unknown", s)))?;
+    }
+    Ok(a)
+}
+
+/// Returns the HOA file of "automata", whose states are numbered by their
+/// position in the automaton and named by their value. The label of a symbol
+/// negates the propositions of the other symbols, so that labels are mutually
+/// exclusive
+pub fn to_hoa<'a, T, V>(
+    automata: &Automata<'a, T, V>,
+) -> std::result::Result<String, std::fmt::Error>
+where
+    T: Eq + Hash + Clone + Display,
+    V: Eq + Clone + Display,
+{
+    let inner = automata.inner();
+    let mut symbols: Vec<&T> = Vec::new();
+    let edges = inner.edges(false);
+    edges.iter().for_each(|(_, s, _)| {
+        if !symbols.contains(&s[0]) {
+            symbols.push(s[0]);
+        }
+    });
+    let index = |rs| inner.states().position(|s| s == rs).unwrap_or_default();
+    let quote = |s: String| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut f = String::new();
+    writeln!(f, "HOA: v1\nStates: {}", inner.states_count())?;
+    inner
+        .inputs()
+        .try_for_each(|rs| writeln!(f, "Start: {}", index(rs)))?;
+    write!(f, "AP: {}", symbols.len())?;
+    symbols
+        .iter()
+        .try_for_each(|s| write!(f, " {}", quote(s.to_string())))?;
+    writeln!(f)?;
+    writeln!(
+        f,
+        "acc-name: Buchi\nAcceptance: 1 Inf(0)\nproperties: state-acc\n--BODY--"
+    )?;
+    inner.states().enumerate().try_for_each(|(i, rs)| {
+        let accepting = match inner.outputs().any(|o| o == rs) {
+            true => " {0}",
+            false => "",
+        };
+        writeln!(
+            f,
+            "State: {} {}{}",
+            i,
+            quote(rs.as_ref().get_value().to_string()),
+            accepting
+        )?;
+        edges
+            .iter()
+            .filter(|(from, _, _)| *from == rs)
+            .try_for_each(|(_, s, to)| {
+                let ap = symbols.iter().position(|t| *t == s[0]).unwrap_or_default();
+                let label: Vec<String> = (0..symbols.len())
+                    .map(|i| match i == ap {
+                        true => i.to_string(),
+                        false => format!("!{}", i),
+                    })
+                    .collect();
+                writeln!(f, "[{}] {}", label.join("&"), index(to))
+            })
+    })?;
+    writeln!(f, "--END--")?;
+    Ok(f)
+}
@@ -0,0 +1,144 @@
+//! Module for reading and writing finite automata in the XML format of JFLAP,
+//! the ```.jff``` files. The states are identified by their JFLAP id, and
+//! their positions in the editor are kept apart from the automaton
+
+use super::{xml, FormatError};
+use crate::automata::{r#impl::Inner, AddStates, Automata, InOut, MutTransition, Result};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Write},
+    hash::Hash,
+};
+
+/// Positions of the states in the JFLAP editor, by state
+pub type Positions = HashMap<usize, (f64, f64)>;
+
+/// Distance between two layers when a position is computed
+const LAYER_DISTANCE: f64 = 120.0;
+/// Distance between two states of a layer when a position is computed
+const STATE_DISTANCE: f64 = 90.0;
+/// Position of the first state when a position is computed
+const ORIGIN: (f64, f64) = (60.0, 60.0);
+
+/// Returns the automaton described by the JFLAP file "input" and the positions
+/// of its states that are given. Transitions on the empty word are refused,
+/// and so are files that are not finite automata
+pub fn from_jflap<'a>(input: &str) -> Result<(Automata<'a, String, usize>, Positions)> {
+    let structure = xml::parse(input)?;
+    if structure.name != "structure" {
+        return Err(
+            FormatError::Invalid(String::from("the root element is not 'structure'")).into(),
+        );
+    }
+    match structure.child("type").map(|t| t.text.trim()) {
+        Some("fa") => {}
+        t => {
+            return Err(FormatError::Invalid(format!(
+                "the type of the structure is {}, not 'fa'",
+                t.map_or_else(|| String::from("missing"), |t| format!("'{}'", t))
+            ))
+            .into())
+        }
+    }
+    let automaton = structure.child("automaton").unwrap_or(&structure);
+    let a = Automata::new();
+    let mut positions = Positions::new();
+    for state in automaton.children("state") {
+        let value = state_id(state.attribute("id"), "state", "id")?;
+        if !a.add_state(value) {
+            return Err(
+                FormatError::Invalid(format!("the state id '{}' is duplicated", value)).into(),
+            );
+        }
+        if state.child("initial").is_some() {
+            a.add_input(&value)?;
+        }
+        if state.child("final").is_some() {
+            a.add_output(&value)?;
+        }
+        let coordinate = |name: &str| state.child(name).and_then(|c| c.text.trim().parse().ok());
+        if let (Some(x), Some(y)) = (coordinate("x"), coordinate("y")) {
+            positions.insert(value, (x, y));
+        }
+    }
+    for transition in automaton.children("transition") {
+        let text = |name: &str| transition.child(name).map(|e| e.text.trim());
+        let from = state_id(text("from"), "transition", "from")?;
+        let to = state_id(text("to"), "transition", "to")?;
+        let symbol = transition.child("read").map_or("", |r| r.text.as_str());
+        if symbol.is_empty() {
+            return Err(FormatError::Invalid(format!(
+                "the transition from '{}' to '{}' reads the empty word",
+                from, to
+            ))
+            .into());
+        }
+        a.add_transition(&from, &to, String::from(symbol))?;
+    }
+    Ok((a, positions))
+}
+
+/// Returns the state id "id", which is the field "field" of an "element"
+fn state_id(
+    id: Option<&str>,
+    element: &str,
+    field: &str,
+) -> std::result::Result<usize, FormatError> {
+    let id = id.ok_or_else(|| FormatError::Invalid(format!("a {} has no {}", element, field)))?;
+    id.parse()
+        .map_err(|_| FormatError::Invalid(format!("the state id '{}' is not a number", id)))
+}
+
+/// Returns the JFLAP file of "automata". The states are placed at their
+/// position in "positions", and the others are placed by a layered layout
+pub fn to_jflap<'a, T>(
+    automata: &Automata<'a, T, usize>,
+    positions: &Positions,
+) -> std::result::Result<String, std::fmt::Error>
+where
+    T: Eq + Hash + Clone + Display,
+{
+    let inner = automata.inner();
+    let layout = inner.layout();
+    let mut f = String::new();
+    writeln!(
+        f,
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\
+        <!--Created with glushkovizer.--><structure>"
+    )?;
+    writeln!(f, "\t<type>fa</type>\n\t<automaton>")?;
+    inner.states().enumerate().try_for_each(|(i, rs)| {
+        let value = rs.as_ref().get_value();
+        let (layer, rank) = layout.positions[i];
+        let (x, y) = positions.get(value).copied().unwrap_or((
+            ORIGIN.0 + layer as f64 * LAYER_DISTANCE,
+            ORIGIN.1 + rank as f64 * STATE_DISTANCE,
+        ));
+        writeln!(f, "\t\t<state id=\"{}\" name=\"q{}\">", value, value)?;
+        writeln!(f, "\t\t\t<x>{:?}</x>\n\t\t\t<y>{:?}</y>", x, y)?;
+        if inner.inputs().any(|i| i == rs) {
+            writeln!(f, "\t\t\t<initial/>")?;
+        }
+        if inner.outputs().any(|o| o == rs) {
+            writeln!(f, "\t\t\t<final/>")?;
+        }
+        writeln!(f, "\t\t</state>")
+    })?;
+    inner
+        .edges(false)
+        .into_iter()
+        .try_for_each(|(from, symbols, to)| {
+            symbols.into_iter().try_for_each(|symbol| {
+                writeln!(
+                    f,
+                    "\t\t<transition>\n\t\t\t<from>{}</from>\n\t\t\t<to>{}</to>\n\t\t\t\
+                    <read>{}</read>\n\t\t</transition>",
+                    from.as_ref().get_value(),
+                    to.as_ref().get_value(),
+                    xml::escape(&symbol.to_string())
+                )
+            })
+        })?;
+    writeln!(f, "\t</automaton>\n</structure>")?;
+    Ok(f)
+}
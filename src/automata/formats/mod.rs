@@ -0,0 +1,32 @@
+//! Module for reading and writing automata in formats of other tools
+
+pub mod att;
+pub mod dot;
+pub mod hoa;
+pub mod jflap;
+pub(crate) mod xml;
+
+pub use att::{from_att, to_att};
+pub use dot::from_dot;
+pub use hoa::{from_hoa, to_hoa};
+pub use jflap::{from_jflap, to_jflap, Positions};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+/// Enumeration of possible errors when reading or writing an automaton
+pub enum FormatError {
+    #[error("Syntax error at line {line}, column {column}: {message}")]
+    /// Error representing the fact that the input is not well-formed
+    Syntax {
+        /// Line of the error, starting from 1
+        line: usize,
+        /// Column of the error, starting from 1
+        column: usize,
+        /// Description of the error
+        message: String,
+    },
+    #[error("Invalid automaton: {0}")]
+    /// Error representing the fact that the input is well-formed but does not
+    /// describe an automaton, or that an automaton cannot be written in a
+    /// format
+    Invalid(String),
+}
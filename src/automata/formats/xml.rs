@@ -0,0 +1,240 @@
+//! Minimal XML reader, sufficient for the formats of automata. Declarations,
+//! comments, processing instructions and document types are skipped, and the
+//! text of an element is the concatenation of its text and CDATA sections
+
+use super::FormatError;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// Element of an XML document
+pub struct Element {
+    /// Name of the element
+    pub name: String,
+    /// Attributes of the element, in order of appearance
+    pub attributes: Vec<(String, String)>,
+    /// Child elements, in order of appearance
+    pub children: Vec<Element>,
+    /// Text directly contained in the element, entities replaced
+    pub text: String,
+}
+
+impl Element {
+    /// Returns the value of the attribute "name", if any
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the first child named "name", if any
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// Returns the children named "name"
+    pub fn children<'b>(&'b self, name: &'b str) -> impl Iterator<Item = &'b Element> {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+}
+
+/// Parser of an XML document
+struct Parser<'s> {
+    input: &'s str,
+    pos: usize,
+}
+
+impl<'s> Parser<'s> {
+    /// Returns a syntax error at the current position
+    fn error(&self, message: &str) -> FormatError {
+        let before = &self.input[..self.pos];
+        FormatError::Syntax {
+            line: before.matches('\n').count() + 1,
+            column: before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1,
+            message: String::from(message),
+        }
+    }
+
+    /// Returns the remaining input
+    fn rest(&self) -> &'s str {
+        &self.input[self.pos..]
+    }
+
+    /// Skips the whitespaces
+    fn skip_whitespaces(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips the input until "end" included, failing with "message" if it is
+    /// not found
+    fn skip_past(&mut self, end: &str, message: &str) -> Result<&'s str, FormatError> {
+        match self.rest().find(end) {
+            None => Err(self.error(message)),
+            Some(i) => {
+                let skipped = &self.rest()[..i];
+                self.pos += i + end.len();
+                Ok(skipped)
+            }
+        }
+    }
+
+    /// Skips the whitespaces, comments, processing instructions and document
+    /// types
+    fn skip_misc(&mut self) -> Result<(), FormatError> {
+        loop {
+            self.skip_whitespaces();
+            if self.rest().starts_with("<?") {
+                self.skip_past("?>", "unterminated processing instruction")?;
+            } else if self.rest().starts_with("<!--") {
+                self.skip_past("-->", "unterminated comment")?;
+            } else if self.rest().starts_with("<!") && !self.rest().starts_with("<![CDATA[") {
+                self.skip_past(">", "unterminated declaration")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Consumes and returns a name
+    fn name(&mut self) -> Result<String, FormatError> {
+        let len = self
+            .rest()
+            .find(|c: char| !(c.is_alphanumeric() || "_-.:".contains(c)))
+            .unwrap_or(self.rest().len());
+        if len == 0 {
+            return Err(self.error("expected a name"));
+        }
+        let name = String::from(&self.rest()[..len]);
+        self.pos += len;
+        Ok(name)
+    }
+
+    /// Returns "s" where the entities are replaced
+    fn unescape(&self, s: &str) -> Result<String, FormatError> {
+        let mut res = String::with_capacity(s.len());
+        let mut parts = s.split('&');
+        res.push_str(parts.next().unwrap_or_default());
+        for part in parts {
+            let (entity, after) = part
+                .split_once(';')
+                .ok_or_else(|| self.error("unterminated entity"))?;
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => match (entity.strip_prefix("#x"), entity.strip_prefix('#')) {
+                    (Some(hex), _) => u32::from_str_radix(hex, 16).ok(),
+                    (None, Some(dec)) => dec.parse().ok(),
+                    (None, None) => None,
+                }
+                .and_then(char::from_u32),
+            };
+            match c {
+                None => return Err(self.error(&format!("unknown entity '&{};'", entity))),
+                Some(c) => res.push(c),
+            }
+            res.push_str(after);
+        }
+        Ok(res)
+    }
+
+    /// Parses an element, the input being on its opening bracket
+    fn element(&mut self) -> Result<Element, FormatError> {
+        if !self.rest().starts_with('<') {
+            return Err(self.error("expected an element"));
+        }
+        self.pos += 1;
+        let mut element = Element {
+            name: self.name()?,
+            ..Default::default()
+        };
+        loop {
+            self.skip_whitespaces();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            }
+            if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let name = self.name()?;
+            self.skip_whitespaces();
+            if !self.rest().starts_with('=') {
+                return Err(self.error("expected '='"));
+            }
+            self.pos += 1;
+            self.skip_whitespaces();
+            let quote = match self.rest().chars().next() {
+                Some(q) if q == '"' || q == '\'' => q,
+                _ => return Err(self.error("expected a quoted value")),
+            };
+            self.pos += 1;
+            let value = self.skip_past(&quote.to_string(), "unterminated attribute value")?;
+            let value = self.unescape(value)?;
+            element.attributes.push((name, value));
+        }
+        loop {
+            let text_len = self.rest().find('<').unwrap_or(self.rest().len());
+            let text = &self.rest()[..text_len];
+            element.text.push_str(&self.unescape(text)?);
+            self.pos += text_len;
+            if self.rest().is_empty() {
+                return Err(self.error(&format!("unclosed element '{}'", element.name)));
+            } else if self.rest().starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                let data = self.skip_past("]]>", "unterminated CDATA section")?;
+                element.text.push_str(data);
+            } else if self.rest().starts_with("<!--") || self.rest().starts_with("<?") {
+                self.skip_misc()?;
+            } else if self.rest().starts_with("</") {
+                self.pos += 2;
+                let name = self.name()?;
+                if name != element.name {
+                    return Err(self.error(&format!(
+                        "expected '</{}>', found '</{}>'",
+                        element.name, name
+                    )));
+                }
+                self.skip_whitespaces();
+                if !self.rest().starts_with('>') {
+                    return Err(self.error("expected '>'"));
+                }
+                self.pos += 1;
+                return Ok(element);
+            } else {
+                element.children.push(self.element()?);
+            }
+        }
+    }
+}
+
+/// Returns the root element of the XML document "input"
+pub fn parse(input: &str) -> Result<Element, FormatError> {
+    let mut parser = Parser { input, pos: 0 };
+    parser.skip_misc()?;
+    let root = parser.element()?;
+    parser.skip_misc()?;
+    match parser.rest().is_empty() {
+        true => Ok(root),
+        false => Err(parser.error("expected the end of the document")),
+    }
+}
+
+/// Returns "s" where the characters that are special in XML are escaped
+pub fn escape(s: &str) -> String {
+    s.chars()
+        .fold(String::with_capacity(s.len()), |mut acc, c| {
+            match c {
+                '&' => acc.push_str("&amp;"),
+                '<' => acc.push_str("&lt;"),
+                '>' => acc.push_str("&gt;"),
+                '"' => acc.push_str("&quot;"),
+                '\'' => acc.push_str("&apos;"),
+                c => acc.push(c),
+            }
+            acc
+        })
+}
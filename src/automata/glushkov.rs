@@ -19,6 +19,8 @@ where
         unsafe {
             for i in 1..end {
                 if let Some((_, l)) = info.follows.iter().find(|&(s, _)| s.1 == i) {
+                    let mut l: Vec<_> = l.iter().collect();
+                    l.sort_by_key(|next| next.1);
                     for next in l {
                         g.add_transition(&i, &next.1, next.0.clone())
                             .unwrap_unchecked();
@@ -28,10 +30,14 @@ where
             if info.null {
                 g.add_final(&0).unwrap_unchecked();
             }
-            for f in info.lasts {
+            let mut lasts: Vec<_> = info.lasts.into_iter().collect();
+            lasts.sort_by_key(|f| f.1);
+            for f in lasts {
                 g.add_final(&f.1).unwrap_unchecked();
             }
-            for i in info.firsts {
+            let mut firsts: Vec<_> = info.firsts.into_iter().collect();
+            firsts.sort_by_key(|i| i.1);
+            for i in firsts {
                 let _ = g.add_transition(&0, &i.1, i.0);
             }
             let _ = g.add_initial(&0);
@@ -1,4 +1,5 @@
 use super::*;
+use indexmap::IndexSet;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{cell::UnsafeCell, hash::Hash};
 
@@ -214,6 +215,20 @@ where
 {
 }
 
+impl<'a, T, V, U> Search<'a, T, V> for U
+where
+    T: Eq + Hash + Clone,
+    U: Inner<'a, T, V>,
+{
+}
+
+impl<'a, V, U> ToRust<'a, V> for U
+where
+    V: Eq + Clone,
+    U: Inner<'a, char, V>,
+{
+}
+
 impl<'a, T, V, U> ToDot<'a, T, V> for U
 where
     T: Eq + Hash + Clone + Display,
@@ -222,6 +237,38 @@ where
 {
 }
 
+impl<'a, T, V, U> ToMermaid<'a, T, V> for U
+where
+    T: Eq + Hash + Clone + Display,
+    V: Eq + Clone + Display,
+    U: Inner<'a, T, V>,
+{
+}
+
+impl<'a, T, V, U> ToGraphML<'a, T, V> for U
+where
+    T: Eq + Hash + Clone + Display,
+    V: Eq + Clone + Display,
+    U: Inner<'a, T, V>,
+{
+}
+
+impl<'a, T, V, U> ToSvg<'a, T, V> for U
+where
+    T: Eq + Hash + Clone + Display,
+    V: Eq + Clone + Display,
+    U: Inner<'a, T, V>,
+{
+}
+
+impl<'a, T, V, U> ToTikz<'a, T, V> for U
+where
+    T: Eq + Hash + Clone + Display,
+    V: Eq + Clone + Display,
+    U: Inner<'a, T, V>,
+{
+}
+
 fimpl!(
     Automata => ExtractSubAutomata {
         fn subautomata(
@@ -233,7 +280,7 @@ fimpl!(
             let inner = self.inner();
             let states = states
                 .into_iter()
-                .try_fold(HashSet::new(), |mut acc, state| {
+                .try_fold(IndexSet::new(), |mut acc, state| {
                     let state = match inner.get_state(state) {
                         None => Err(AutomataError::UnknowState),
                         Some(rs) => Ok(rs),
@@ -245,7 +292,7 @@ fimpl!(
                 })?;
             let inputs = inputs
                 .into_iter()
-                .try_fold(HashSet::new(), |mut acc, input| {
+                .try_fold(IndexSet::new(), |mut acc, input| {
                     let input = match inner.get_state(input) {
                         None => Err(AutomataError::UnknowState),
                         Some(rs) => Ok(rs),
@@ -260,7 +307,7 @@ fimpl!(
                 })?;
             let outputs = outputs
                 .into_iter()
-                .try_fold(HashSet::new(), |mut acc, output| {
+                .try_fold(IndexSet::new(), |mut acc, output| {
                     let output = match inner.get_state(output) {
                         None => Err(AutomataError::UnknowState),
                         Some(rs) => Ok(rs),
@@ -294,7 +341,7 @@ fimpl!(
             let inner = self.inner();
             let states = states
                 .into_iter()
-                .try_fold(HashSet::new(), |mut acc, state| {
+                .try_fold(IndexSet::new(), |mut acc, state| {
                     let state = match inner.get_state(state) {
                         None => Err(AutomataError::UnknowState),
                         Some(rs) => Ok(rs),
@@ -306,7 +353,7 @@ fimpl!(
                 })?;
             let inputs = inputs
                 .into_iter()
-                .try_fold(HashSet::new(), |mut acc, input| {
+                .try_fold(IndexSet::new(), |mut acc, input| {
                     let input = match inner.get_state(input) {
                         None => Err(AutomataError::UnknowState),
                         Some(rs) => Ok(rs),
@@ -321,7 +368,7 @@ fimpl!(
                 })?;
             let outputs = outputs
                 .into_iter()
-                .try_fold(HashSet::new(), |mut acc, output| {
+                .try_fold(IndexSet::new(), |mut acc, output| {
                     let output = match inner.get_state(output) {
                         None => Err(AutomataError::UnknowState),
                         Some(rs) => Ok(rs),
@@ -395,10 +442,12 @@ where
     }
 }
 
+/// Deserialization requires [Debug] states and symbols, so that the errors
+/// name the offending one
 impl<'de, 'a, T, V> Deserialize<'de> for Automata<'a, T, V>
 where
-    T: Deserialize<'de> + Eq + Hash + Clone + 'a,
-    V: Deserialize<'de> + Eq + Clone + 'a,
+    T: Deserialize<'de> + Eq + Hash + Clone + Debug + 'a,
+    V: Deserialize<'de> + Eq + Clone + Debug + 'a,
 {
     fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
@@ -426,8 +475,8 @@ where
 
 impl<'de, 'a, T, V> Deserialize<'de> for InnerParent<'a, T, V>
 where
-    T: Deserialize<'de> + Eq + Hash + Clone + 'a,
-    V: Deserialize<'de> + Eq + Clone + 'a,
+    T: Deserialize<'de> + Eq + Hash + Clone + Debug + 'a,
+    V: Deserialize<'de> + Eq + Clone + Debug + 'a,
 {
     fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
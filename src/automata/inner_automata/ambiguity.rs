@@ -0,0 +1,283 @@
+//! Module for the ambiguity analysis of an automaton, using the strongly
+//! connected components of its square and the paths of its cube
+
+use super::{state::RefState, utils::Couple, InnerAutomata};
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Degree of ambiguity of an automaton, i.e. the growth of the number of
+/// accepting runs of a word with its length
+pub enum AmbiguityDegree {
+    /// Each accepted word has exactly one accepting run
+    Unambiguous,
+    /// The number of accepting runs of a word is bounded
+    Finite,
+    /// The number of accepting runs of a word is bounded by a polynomial of
+    /// its length
+    Polynomial,
+    /// The number of accepting runs of a word can be exponential in its
+    /// length
+    Exponential,
+}
+
+/// State of the square of an automaton, with whether the two runs leading to
+/// it have diverged
+type Diverged<'b, 'a, T> = (&'b RefState<'a, T, Couple<usize, usize>>, bool);
+
+/// Predecessor relation of a breadth-first search in the square of an automaton
+type Predecessor<'b, 'a, T> = HashMap<Diverged<'b, 'a, T>, Option<(Diverged<'b, 'a, T>, &'b T)>>;
+
+/// Structure representing the useful part of an automaton, where states are
+/// numbered
+struct Trimmed<T> {
+    /// Transitions of each state
+    follows: Vec<Vec<(T, usize)>>,
+    /// Input states
+    inputs: Vec<usize>,
+    /// Whether each state is an output
+    outputs: Vec<bool>,
+}
+
+impl<'a, T, V> InnerAutomata<'a, T, V>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Returns the useful part of the automaton, i.e. its states both
+    /// accessible and coaccessible
+    fn trimmed(&self) -> Trimmed<T> {
+        let states: Vec<&RefState<'a, T, V>> = self.states().collect();
+        let index: HashMap<&RefState<'a, T, V>, usize> =
+            states.iter().enumerate().map(|(i, &rs)| (rs, i)).collect();
+        let follows: Vec<Vec<(T, usize)>> = states
+            .iter()
+            .map(|rs| {
+                rs.as_ref()
+                    .get_follows()
+                    .flat_map(|(symbol, set)| {
+                        set.iter()
+                            .filter_map(|to| index.get(to).map(|&to| (symbol.clone(), to)))
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut previous: Vec<Vec<usize>> = vec![Vec::new(); states.len()];
+        follows.iter().enumerate().for_each(|(from, l)| {
+            l.iter().for_each(|(_, to)| previous[*to].push(from));
+        });
+        let accessible = reach(self.inputs().filter_map(|rs| index.get(rs).copied()), |i| {
+            follows[i].iter().map(|(_, to)| *to).collect()
+        });
+        let coaccessible = reach(
+            self.outputs().filter_map(|rs| index.get(rs).copied()),
+            |i| previous[i].clone(),
+        );
+        let useful: Vec<bool> = (0..states.len())
+            .map(|i| accessible.contains(&i) && coaccessible.contains(&i))
+            .collect();
+        Trimmed {
+            follows: follows
+                .into_iter()
+                .enumerate()
+                .map(|(from, l)| match useful[from] {
+                    true => l.into_iter().filter(|(_, to)| useful[*to]).collect(),
+                    false => Vec::new(),
+                })
+                .collect(),
+            inputs: (0..states.len())
+                .filter(|&i| useful[i] && self.is_input(states[i]))
+                .collect(),
+            outputs: states
+                .iter()
+                .enumerate()
+                .map(|(i, rs)| useful[i] && self.is_output(rs))
+                .collect(),
+        }
+    }
+
+    /// Returns the useful part of the square of the automaton, whose states
+    /// are the pairs of states reached by a same word, accompanied by the
+    /// useful part of the automaton
+    fn square(&self) -> (InnerAutomata<'a, T, Couple<usize, usize>>, Trimmed<T>) {
+        let trimmed = self.trimmed();
+        let starts: Vec<(usize, usize)> = trimmed
+            .inputs
+            .iter()
+            .flat_map(|&p| trimmed.inputs.iter().map(move |&q| (p, q)))
+            .collect();
+        let successors = |(p, q): (usize, usize)| -> Vec<(T, (usize, usize))> {
+            trimmed.follows[p]
+                .iter()
+                .flat_map(|(a, p2)| {
+                    trimmed.follows[q]
+                        .iter()
+                        .filter(move |(b, _)| a == b)
+                        .map(move |(_, q2)| (a.clone(), (*p2, *q2)))
+                })
+                .collect()
+        };
+        let accessible = reach(starts.iter().copied(), |pair| {
+            successors(pair).into_iter().map(|(_, to)| to).collect()
+        });
+        let mut previous: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
+        accessible.iter().for_each(|&from| {
+            successors(from).into_iter().for_each(|(_, to)| {
+                previous.entry(to).or_default().push(from);
+            })
+        });
+        let coaccessible = reach(
+            accessible
+                .iter()
+                .copied()
+                .filter(|&(p, q)| trimmed.outputs[p] && trimmed.outputs[q]),
+            |pair| previous.get(&pair).cloned().unwrap_or_default(),
+        );
+        let mut res = InnerAutomata::new();
+        let mut pairs: Vec<(usize, usize)> =
+            accessible.intersection(&coaccessible).copied().collect();
+        pairs.sort_unstable();
+        let refs: HashMap<(usize, usize), RefState<'a, T, Couple<usize, usize>>> = pairs
+            .iter()
+            .map(|&(p, q)| {
+                let rs = RefState::new(Couple(p, q));
+                let r = rs.clone();
+                res.add_state(rs);
+                ((p, q), r)
+            })
+            .collect();
+        pairs.iter().for_each(|&(p, q)| {
+            let rs = &refs[&(p, q)];
+            if starts.contains(&(p, q)) {
+                res.add_input(rs.clone());
+            }
+            if trimmed.outputs[p] && trimmed.outputs[q] {
+                res.add_output(rs.clone());
+            }
+            successors((p, q)).into_iter().for_each(|(symbol, to)| {
+                if let Some(to) = refs.get(&to) {
+                    rs.add_follow(to.clone(), symbol);
+                }
+            });
+        });
+        (res, trimmed)
+    }
+
+    /// Returns a shortest word with at least two accepting runs, or ```None```
+    /// if the automaton is unambiguous. The breadth-first search runs in the
+    /// square from all its inputs at once, each pair remembering whether the
+    /// two runs leading to it have already diverged
+    pub fn ambiguous_word(&self) -> Option<Vec<T>> {
+        let (square, _) = self.square();
+        let diagonal = |rs: &RefState<'a, T, Couple<usize, usize>>| {
+            let Couple(p, q) = rs.as_ref().get_value();
+            p == q
+        };
+        let mut predecessor: Predecessor<'_, 'a, T> = square
+            .inputs()
+            .map(|rs| ((rs, !diagonal(rs)), None))
+            .collect();
+        let mut queue: VecDeque<Diverged<'_, 'a, T>> = predecessor.keys().copied().collect();
+        let end = loop {
+            let (rs, diverged) = queue.pop_front()?;
+            if diverged && square.is_output(rs) {
+                break (rs, diverged);
+            }
+            rs.as_ref().get_follows().for_each(|(symbol, set)| {
+                set.iter().for_each(|to| {
+                    let to = (to, diverged || !diagonal(to));
+                    if let Entry::Vacant(e) = predecessor.entry(to) {
+                        e.insert(Some(((rs, diverged), symbol)));
+                        queue.push_back(to);
+                    }
+                })
+            });
+        };
+        let mut word = Vec::new();
+        let mut cur = end;
+        while let Some(Some((from, symbol))) = predecessor.get(&cur) {
+            word.push((*symbol).clone());
+            cur = *from;
+        }
+        word.reverse();
+        Some(word)
+    }
+
+    /// Returns whether each accepted word has exactly one accepting run
+    pub fn is_unambiguous(&self) -> bool {
+        self.square().0.states().all(|rs| {
+            let Couple(p, q) = rs.as_ref().get_value();
+            p == q
+        })
+    }
+
+    /// Returns the degree of ambiguity of the automaton
+    pub fn ambiguity_degree(&self) -> AmbiguityDegree {
+        let (square, trimmed) = self.square();
+        if square.states().all(|rs| {
+            let Couple(p, q) = rs.as_ref().get_value();
+            p == q
+        }) {
+            return AmbiguityDegree::Unambiguous;
+        }
+        let exponential = square.kosaraju().into_iter().any(|scc| {
+            let (diagonal, other): (Vec<_>, Vec<_>) = scc.iter().partition(|rs| {
+                let Couple(p, q) = rs.as_ref().get_value();
+                p == q
+            });
+            !diagonal.is_empty() && !other.is_empty()
+        });
+        if exponential {
+            return AmbiguityDegree::Exponential;
+        }
+        let follows = &trimmed.follows;
+        let useful: Vec<usize> = (0..follows.len())
+            .filter(|&p| !follows[p].is_empty() || trimmed.outputs[p])
+            .collect();
+        let cube = |(x, y, z): (usize, usize, usize)| -> Vec<(usize, usize, usize)> {
+            follows[x]
+                .iter()
+                .flat_map(|(a, x2)| {
+                    follows[y]
+                        .iter()
+                        .filter(move |(b, _)| a == b)
+                        .flat_map(move |(_, y2)| {
+                            follows[z]
+                                .iter()
+                                .filter(move |(c, _)| a == c)
+                                .map(move |(_, z2)| (*x2, *y2, *z2))
+                        })
+                })
+                .collect()
+        };
+        let polynomial = useful.iter().any(|&p| {
+            useful
+                .iter()
+                .any(|&q| p != q && reach([(p, p, q)], cube).contains(&(p, q, q)))
+        });
+        if polynomial {
+            AmbiguityDegree::Polynomial
+        } else {
+            AmbiguityDegree::Finite
+        }
+    }
+}
+
+/// Returns the set of nodes reachable from "starts" with the successor
+/// function "next"
+fn reach<N, I, F>(starts: I, next: F) -> HashSet<N>
+where
+    N: Eq + Hash + Copy,
+    I: IntoIterator<Item = N>,
+    F: Fn(N) -> Vec<N>,
+{
+    let mut seen: HashSet<N> = HashSet::new();
+    let mut stack: Vec<N> = starts.into_iter().collect();
+    while let Some(n) = stack.pop() {
+        if seen.insert(n) {
+            stack.extend(next(n).into_iter().filter(|m| !seen.contains(m)));
+        }
+    }
+    seen
+}
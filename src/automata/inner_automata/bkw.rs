@@ -0,0 +1,267 @@
+//! Module for deciding, with the algorithm of Brüggemann-Klein and Wood,
+//! whether the language of an automaton is described by a one-unambiguous
+//! regular expression, and for building this expression
+
+use super::{door::DoorType, state::RefState, InnerAutomata};
+use crate::regexp::RegExp;
+use std::{collections::HashMap, hash::Hash};
+
+/// Expressions already computed by the orbit expression of each state
+type Cache<'a, T> = HashMap<RefState<'a, T, usize>, Option<RegExp<T>>>;
+
+/// Returns the concatenation of "l" and "r", without useless epsilon
+fn concat<T>(l: RegExp<T>, r: RegExp<T>) -> RegExp<T> {
+    match (l, r) {
+        (RegExp::Epsilon, e) | (e, RegExp::Epsilon) => e,
+        (l, r) => RegExp::Concat(Box::new(l), Box::new(r)),
+    }
+}
+
+/// Returns the "or" of the non empty list "terms", associated to the left
+fn or<T>(terms: Vec<RegExp<T>>) -> RegExp<T> {
+    let mut terms = terms.into_iter();
+    let first = terms.next().unwrap_or(RegExp::Epsilon);
+    terms.fold(first, |acc, e| RegExp::Or(Box::new(acc), Box::new(e)))
+}
+
+impl<'a, T, V> InnerAutomata<'a, T, V>
+where
+    T: Eq + Hash + Clone,
+    V: Eq + Clone,
+{
+    /// Returns a one-unambiguous regular expression recognizing the same
+    /// language as the automaton, or ```None``` if there is none or if the
+    /// language is empty
+    pub fn to_one_unambiguous(&self) -> Option<RegExp<T>> {
+        let minimal = self.minimize();
+        if minimal.outputs_count() == 0 {
+            return None;
+        }
+        minimal.bkw()
+    }
+}
+
+impl<'a, T> InnerAutomata<'a, T, usize>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Returns the symbols consistent with the minimal automaton, i.e. the
+    /// symbols "a" for which all the outputs go to the same state "f(a)" with
+    /// "a", accompanied by this state
+    fn consistent_symbols(&self) -> Vec<(T, RefState<'a, T, usize>)> {
+        let first = match self.outputs().next() {
+            None => return Vec::new(),
+            Some(rs) => rs,
+        };
+        first
+            .as_ref()
+            .get_follows()
+            .filter_map(|(symbol, set)| {
+                let to = set.iter().find(|rs| self.states.contains(*rs))?;
+                self.outputs()
+                    .all(|rs| {
+                        rs.as_ref()
+                            .get_follow(symbol)
+                            .is_some_and(|mut it| it.any(|rs| rs == to))
+                    })
+                    .then(|| (symbol.clone(), to.clone()))
+            })
+            .collect()
+    }
+
+    /// Returns whether the automaton, whose strongly connected components
+    /// and their doors are "doors", has the orbit property. That is to say
+    /// whether in each orbit all the gates are outputs or none of them, and
+    /// all the gates have the same transitions leaving the orbit
+    fn has_orbit_property(&self, doors: &[Vec<(RefState<'a, T, usize>, DoorType)>]) -> bool {
+        doors.iter().all(|orbit| {
+            let gates: Vec<&RefState<'a, T, usize>> = gates(orbit).collect();
+            let first = match gates.first() {
+                None => return true,
+                Some(&rs) => rs,
+            };
+            gates.iter().all(|&gate| {
+                self.is_output(gate) == self.is_output(first)
+                    && same_exits(orbit, gate, first)
+                    && same_exits(orbit, first, gate)
+            })
+        })
+    }
+
+    /// Returns the orbit automaton of the state "state", whose states are the
+    /// ones of "orbit", the input is "state" and the outputs are the gates of
+    /// "orbit"
+    fn orbit_automaton(
+        &self,
+        orbit: &[(RefState<'a, T, usize>, DoorType)],
+        state: &RefState<'a, T, usize>,
+    ) -> InnerAutomata<'a, T, usize> {
+        let mut res = InnerAutomata::new();
+        let states: Vec<RefState<'a, T, usize>> = orbit
+            .iter()
+            .map(|(rs, _)| RefState::new(*rs.as_ref().get_value()))
+            .collect();
+        orbit
+            .iter()
+            .zip(states.iter())
+            .for_each(|((from, dt), sfrom)| {
+                if from == state {
+                    res.add_input(sfrom.clone());
+                }
+                if let DoorType::Out | DoorType::Both = dt {
+                    res.add_output(sfrom.clone());
+                }
+                from.as_ref().get_follows().for_each(|(symbol, set)| {
+                    set.iter().for_each(|to| {
+                        if let Some(i) = orbit.iter().position(|(rs, _)| rs == to) {
+                            sfrom.add_follow(states[i].clone(), symbol.clone());
+                        }
+                    })
+                })
+            });
+        states.into_iter().for_each(|rs| {
+            res.add_state(rs);
+        });
+        res
+    }
+
+    /// Returns a one-unambiguous regular expression recognizing the language
+    /// of the automaton starting from "state", or ```None``` if there is
+    /// none. The automaton, whose strongly connected components and their
+    /// doors are "doors", must have the orbit property. The expressions are
+    /// kept in "cache", since the orbits following a gate are reached from
+    /// every orbit before them
+    fn orbit_expression(
+        &self,
+        doors: &[Vec<(RefState<'a, T, usize>, DoorType)>],
+        state: &RefState<'a, T, usize>,
+        cache: &mut Cache<'a, T>,
+    ) -> Option<RegExp<T>> {
+        if let Some(res) = cache.get(state) {
+            return res.clone();
+        }
+        let res = self.orbit_expression_uncached(doors, state, cache);
+        cache.insert(state.clone(), res.clone());
+        res
+    }
+
+    /// Auxiliary function of [Self::orbit_expression] computing the
+    /// expression of "state"
+    fn orbit_expression_uncached(
+        &self,
+        doors: &[Vec<(RefState<'a, T, usize>, DoorType)>],
+        state: &RefState<'a, T, usize>,
+        cache: &mut Cache<'a, T>,
+    ) -> Option<RegExp<T>> {
+        let orbit = doors
+            .iter()
+            .find(|orbit| orbit.iter().any(|(rs, _)| rs == state))?;
+        let sub = self.orbit_automaton(orbit, state);
+        let head = if sub.is_orbit() {
+            sub.minimize().bkw()?
+        } else {
+            RegExp::Epsilon
+        };
+        let gate = gates(orbit).next()?;
+        // Symbols leaving the gate, grouped by the state they lead to so that
+        // the expression of this state is written once
+        let mut leaving: Vec<(_, Vec<RegExp<T>>)> = Vec::new();
+        for (symbol, set) in gate.as_ref().get_follows() {
+            for to in set.iter() {
+                if self.states.contains(to) && !orbit.iter().any(|(rs, _)| rs == to) {
+                    match leaving.iter_mut().find(|(rs, _)| rs == &to) {
+                        Some((_, symbols)) => symbols.push(RegExp::Symbol(symbol.clone())),
+                        None => leaving.push((to, vec![RegExp::Symbol(symbol.clone())])),
+                    }
+                }
+            }
+        }
+        let mut tail = Vec::new();
+        if self.is_output(gate) {
+            tail.push(RegExp::Epsilon);
+        }
+        for (to, symbols) in leaving {
+            let next = self.orbit_expression(doors, to, cache)?;
+            tail.push(concat(or(symbols), next));
+        }
+        Some(concat(head, or(tail)))
+    }
+
+    /// Returns a one-unambiguous regular expression recognizing the language
+    /// of the minimal automaton, or ```None``` if there is none
+    fn bkw(&self) -> Option<RegExp<T>> {
+        let input = self.inputs().next()?;
+        let consistent = self.consistent_symbols();
+        if consistent.is_empty() && self.is_orbit() {
+            return None;
+        }
+        let cut = self.clone();
+        let consistent: Vec<(T, RefState<'a, T, usize>)> = consistent
+            .into_iter()
+            .filter_map(|(symbol, to)| Some((symbol, cut.get_state(to.as_ref().get_value())?)))
+            .collect();
+        cut.outputs().for_each(|rs| {
+            consistent.iter().for_each(|(symbol, to)| {
+                rs.remove_follow(to, symbol);
+            })
+        });
+        let doors = cut.get_door();
+        if !cut.has_orbit_property(&doors) {
+            return None;
+        }
+        let mut cache = Cache::new();
+        let head = cut.orbit_expression(
+            &doors,
+            &cut.get_state(input.as_ref().get_value())?,
+            &mut cache,
+        )?;
+        if consistent.is_empty() {
+            return Some(head);
+        }
+        let iteration = consistent
+            .iter()
+            .map(|(symbol, to)| {
+                Some(concat(
+                    RegExp::Symbol(symbol.clone()),
+                    cut.orbit_expression(&doors, to, &mut cache)?,
+                ))
+            })
+            .collect::<Option<Vec<RegExp<T>>>>()?;
+        Some(concat(head, RegExp::Repeat(Box::new(or(iteration)))))
+    }
+}
+
+/// Returns the gates of "orbit", i.e. its output doors
+fn gates<'b, 'a, T, V>(
+    orbit: &'b [(RefState<'a, T, V>, DoorType)],
+) -> impl Iterator<Item = &'b RefState<'a, T, V>>
+where
+    T: Eq + Hash + Clone,
+{
+    orbit.iter().filter_map(|(rs, dt)| match dt {
+        DoorType::Out | DoorType::Both => Some(rs),
+        _ => None,
+    })
+}
+
+/// Returns whether all the transitions of "from" leaving "orbit" are also
+/// transitions of "other"
+fn same_exits<'a, T, V>(
+    orbit: &[(RefState<'a, T, V>, DoorType)],
+    from: &RefState<'a, T, V>,
+    other: &RefState<'a, T, V>,
+) -> bool
+where
+    T: Eq + Hash + Clone,
+{
+    from.as_ref().get_follows().all(|(symbol, set)| {
+        set.iter()
+            .filter(|to| !orbit.iter().any(|(rs, _)| rs == *to))
+            .all(|to| {
+                other
+                    .as_ref()
+                    .get_follow(symbol)
+                    .is_some_and(|mut it| it.any(|rs| rs == to))
+            })
+    })
+}
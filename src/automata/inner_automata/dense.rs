@@ -0,0 +1,191 @@
+//! Module for compiling an automaton into a dense transition table, allowing
+//! the recognition of a word without hashing
+
+use super::InnerAutomata;
+use crate::automata::AutomataError;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    try_from = "RawDenseDfa<T>",
+    bound(deserialize = "T: Ord + Deserialize<'de>")
+)]
+/// Structure representing a deterministic automaton as a table. States are
+/// numbered from 0, the initial state, and symbols are columns given by their
+/// rank in the sorted alphabet
+pub struct DenseDfa<T> {
+    /// Sorted alphabet, the column of a symbol is its index
+    symbols: Vec<T>,
+    /// Transition table, the successor of the state s by the symbol of column
+    /// c is at s * symbols.len() + c, or [DenseDfa::DEAD] if there is none
+    table: Vec<u32>,
+    /// Whether each state is final
+    finals: Vec<bool>,
+}
+
+#[derive(Deserialize)]
+/// Unchecked fields of a [DenseDfa], used for deserialization
+struct RawDenseDfa<T> {
+    symbols: Vec<T>,
+    table: Vec<u32>,
+    finals: Vec<bool>,
+}
+
+impl<T: Ord> TryFrom<RawDenseDfa<T>> for DenseDfa<T> {
+    type Error = AutomataError;
+
+    fn try_from(raw: RawDenseDfa<T>) -> Result<Self, Self::Error> {
+        DenseDfa::from_raw_parts(raw.symbols, raw.table, raw.finals)
+    }
+}
+
+impl<T> DenseDfa<T>
+where
+    T: Ord,
+{
+    /// Marker of the absence of transition in the table
+    pub const DEAD: u32 = u32::MAX;
+
+    /// Creates a table from its parts, see [DenseDfa::symbols],
+    /// [DenseDfa::table] and [DenseDfa::finals]. Returns an error if
+    /// "symbols" is not strictly sorted, if there is no state or if the table
+    /// does not have one row per state or refers to an unknown state
+    pub fn from_raw_parts(
+        symbols: Vec<T>,
+        table: Vec<u32>,
+        finals: Vec<bool>,
+    ) -> Result<Self, AutomataError> {
+        if finals.is_empty()
+            || symbols.windows(2).any(|w| w[0] >= w[1])
+            || table.len() != finals.len() * symbols.len()
+            || table
+                .iter()
+                .any(|&to| to != Self::DEAD && to as usize >= finals.len())
+        {
+            return Err(AutomataError::InvalidTable);
+        }
+        Ok(Self {
+            symbols,
+            table,
+            finals,
+        })
+    }
+
+    /// Returns the sorted alphabet of the table
+    pub fn symbols(&self) -> &[T] {
+        &self.symbols
+    }
+
+    /// Returns the transition table, row by row
+    pub fn table(&self) -> &[u32] {
+        &self.table
+    }
+
+    /// Returns whether each state is final
+    pub fn finals(&self) -> &[bool] {
+        &self.finals
+    }
+
+    /// Returns the number of states
+    pub fn states_count(&self) -> usize {
+        self.finals.len()
+    }
+
+    /// Returns the column of "symbol", or ```None``` if it is not in the
+    /// alphabet
+    pub fn column(&self, symbol: &T) -> Option<usize> {
+        self.symbols.binary_search(symbol).ok()
+    }
+
+    /// Returns the successor of "state" by "symbol", or ```None``` if there is
+    /// none
+    pub fn next_state(&self, state: u32, symbol: &T) -> Option<u32> {
+        let column = self.column(symbol)?;
+        match self.table[state as usize * self.symbols.len() + column] {
+            Self::DEAD => None,
+            to => Some(to),
+        }
+    }
+
+    /// Returns whether "state" is final
+    pub fn is_final(&self, state: u32) -> bool {
+        self.finals[state as usize]
+    }
+
+    /// Returns ```true``` if the word is recognized by the automaton and
+    /// ```false``` otherwise
+    pub fn accept<'b>(&self, word: impl IntoIterator<Item = &'b T>) -> bool
+    where
+        T: 'b,
+    {
+        let mut state = 0;
+        for symbol in word {
+            match self.next_state(state, symbol) {
+                None => return false,
+                Some(to) => state = to,
+            }
+        }
+        self.is_final(state)
+    }
+}
+
+impl<'a, T, V> InnerAutomata<'a, T, V>
+where
+    T: Eq + Hash + Clone + Ord,
+    V: Eq + Clone,
+{
+    /// Compiles the minimal deterministic automaton recognizing the same
+    /// language as the current automaton into a [DenseDfa]. States are
+    /// numbered in breadth-first order following the sorted alphabet, so the
+    /// table only depends on the language
+    pub fn compile(&self) -> DenseDfa<T> {
+        let minimal = self.minimize();
+        let mut symbols: Vec<T> = minimal
+            .states()
+            .flat_map(|rs| rs.as_ref().get_follows().map(|(symbol, _)| symbol.clone()))
+            .collect();
+        symbols.sort_unstable();
+        symbols.dedup();
+        let columns: HashMap<&T, usize> = symbols.iter().enumerate().map(|(i, s)| (s, i)).collect();
+        let count = minimal.states_count();
+        let mut delta: Vec<Vec<Option<usize>>> = vec![vec![None; symbols.len()]; count];
+        let mut finals = vec![false; count];
+        minimal.states().for_each(|rs| {
+            let from = *rs.as_ref().get_value();
+            finals[from] = minimal.is_output(rs);
+            rs.as_ref().get_follows().for_each(|(symbol, set)| {
+                delta[from][columns[symbol]] = set.iter().next().map(|to| *to.as_ref().get_value());
+            });
+        });
+        let mut order: Vec<Option<u32>> = vec![None; count];
+        order[0] = Some(0);
+        let mut queue = VecDeque::from([0]);
+        let mut visited = vec![0];
+        while let Some(from) = queue.pop_front() {
+            delta[from].iter().flatten().for_each(|&to| {
+                if order[to].is_none() {
+                    order[to] = Some(visited.len() as u32);
+                    visited.push(to);
+                    queue.push_back(to);
+                }
+            });
+        }
+        DenseDfa {
+            table: visited
+                .iter()
+                .flat_map(|&from| {
+                    delta[from]
+                        .iter()
+                        .map(|to| to.and_then(|to| order[to]).unwrap_or(DenseDfa::<T>::DEAD))
+                        .collect::<Vec<u32>>()
+                })
+                .collect(),
+            finals: visited.iter().map(|&from| finals[from]).collect(),
+            symbols,
+        }
+    }
+}
@@ -3,13 +3,146 @@
 
 use crate::automata::DoorType;
 
-use super::InnerAutomata;
+use super::{state::RefState, InnerAutomata};
 use std::{
     fmt::{Display, Write},
     hash::Hash,
 };
 
-const NB_ATTR: usize = 3;
+const NB_ATTR: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+/// Enumeration of the directions of the layout of a DOT graph
+pub enum RankDir {
+    /// From left to right
+    #[default]
+    LeftRight,
+    /// From right to left
+    RightLeft,
+    /// From top to bottom
+    TopBottom,
+    /// From bottom to top
+    BottomTop,
+}
+
+impl Display for RankDir {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RankDir::LeftRight => write!(f, "LR"),
+            RankDir::RightLeft => write!(f, "RL"),
+            RankDir::TopBottom => write!(f, "TB"),
+            RankDir::BottomTop => write!(f, "BT"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Structure grouping together the options of the DOT representation of an
+/// automaton. The default options give the output of [InnerAutomata::to_dot]
+pub struct DotOptions<T, V> {
+    /// Direction of the layout
+    pub rankdir: RankDir,
+    /// Font of the nodes and edges
+    pub font: String,
+    /// Background color
+    pub background: String,
+    /// Color of the graph, nodes and edges, or ```None``` for the default one
+    /// of DOT
+    pub color: Option<String>,
+    /// Whether the strongly connected components are drawn as clusters
+    pub clusters: bool,
+    /// Colors of the states that are inputs, outputs or both of their
+    /// strongly connected component, or ```None``` to not color doors
+    pub doors: Option<DoorColors>,
+    /// Whether inputs are marked by an incoming arrow instead of a diamond
+    /// shape
+    pub initial_arrows: bool,
+    /// Whether the transitions between two same states are merged in one edge
+    /// labelled by all their symbols, separated by commas
+    pub merge_edges: bool,
+    /// Whether node ids are derived from the values of the states instead of
+    /// their position in the automaton. Values must then have distinct
+    /// representations
+    pub value_ids: bool,
+    /// States to highlight
+    pub highlight_states: Vec<V>,
+    /// Transitions to highlight, given as (from, symbol, to)
+    pub highlight_edges: Vec<(V, T, V)>,
+    /// Color of the highlighted states and transitions
+    pub highlight_color: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Structure grouping together the colors of the doors of the strongly
+/// connected components
+pub struct DoorColors {
+    /// Color of the states that are only inputs of their component
+    pub input: String,
+    /// Color of the states that are only outputs of their component
+    pub output: String,
+    /// Color of the states that are both inputs and outputs of their component
+    pub both: String,
+}
+
+impl Default for DoorColors {
+    fn default() -> Self {
+        Self {
+            input: String::from("red"),
+            output: String::from("blue"),
+            both: String::from("purple"),
+        }
+    }
+}
+
+impl<T, V> Default for DotOptions<T, V> {
+    fn default() -> Self {
+        Self {
+            rankdir: RankDir::default(),
+            font: String::from("Cantarell"),
+            background: String::from("transparent"),
+            color: None,
+            clusters: true,
+            doors: Some(DoorColors::default()),
+            initial_arrows: false,
+            merge_edges: false,
+            value_ids: false,
+            highlight_states: Vec::new(),
+            highlight_edges: Vec::new(),
+            highlight_color: String::from("orange"),
+        }
+    }
+}
+
+/// Transition of an automaton, given as its source, its symbols and its target
+pub(crate) type Edge<'b, 'a, T, V> = (&'b RefState<'a, T, V>, Vec<&'b T>, &'b RefState<'a, T, V>);
+
+impl<'a, T, V> InnerAutomata<'a, T, V>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Returns the transitions of the automaton, ordered by source state then
+    /// by symbol. If "merge" is ```true```, the transitions between two same
+    /// states are merged into one carrying all their symbols, otherwise each
+    /// one carries a single symbol
+    pub(crate) fn edges(&self, merge: bool) -> Vec<Edge<'_, 'a, T, V>> {
+        let mut edges: Vec<Edge<'_, 'a, T, V>> = Vec::new();
+        self.states.iter().for_each(|from| {
+            let start = edges.len();
+            from.as_ref().get_follows().for_each(|(symbol, set)| {
+                set.iter()
+                    .filter(|to| self.states.contains(*to))
+                    .for_each(|to| {
+                        let same = |(f, _, t): &&mut Edge<'_, 'a, T, V>| *f == from && *t == to;
+                        match edges[start..].iter_mut().find(same) {
+                            Some((_, symbols, _)) if merge => symbols.push(symbol),
+                            _ => edges.push((from, vec![symbol], to)),
+                        }
+                    })
+            });
+        });
+        edges
+    }
+}
 
 impl<'a, T, V> InnerAutomata<'a, T, V>
 where
@@ -18,69 +151,132 @@ where
 {
     /// Returns the DOT representation of the automaton with inverted colors if
     /// "inverse" is ``true``
-    pub fn to_dot(&self, inverse: bool) -> Result<String, std::fmt::Error> {
+    pub fn to_dot(&self, inverse: bool) -> Result<String, std::fmt::Error>
+    where
+        V: Eq,
+    {
+        self.to_dot_with(&DotOptions {
+            color: inverse.then(|| String::from("white")),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the DOT representation of the automaton according to "options"
+    pub fn to_dot_with(&self, options: &DotOptions<T, V>) -> Result<String, std::fmt::Error>
+    where
+        V: Eq,
+    {
         let mut f = String::new();
         write!(
             f,
-            "digraph {{\n\trankdir=LR\n\tbgcolor=transparent\n\tnode \
-            [fontname=Cantarell];\n\tedge [fontname=Cantarell];\n"
+            "digraph {{\n\trankdir={}\n\tbgcolor={}\n\tnode \
+            [fontname={}];\n\tedge [fontname={}];\n",
+            options.rankdir,
+            quote(&options.background),
+            quote(&options.font),
+            quote(&options.font)
         )?;
-        if inverse {
+        if let Some(color) = &options.color {
+            let color = quote(color);
             write!(
                 f,
-                "\tcolor=white\n\tnode [color=white, fontcolor=white];\n\tedge [color=white, fontcolor=white];"
+                "\tcolor={}\n\tnode [color={}, fontcolor={}];\n\tedge [color={}, fontcolor={}];",
+                color, color, color, color, color
             )?;
         }
-        let mut attr: Vec<&str> = Vec::with_capacity(NB_ATTR);
+        let id = |rs: &RefState<'a, T, V>| match options.value_ids {
+            true => quote(&rs.as_ref().get_value().to_string()),
+            false => self.states.get_index_of(rs).map_or_else(
+                || format!("\"{}\"", rs.as_ptr() as usize),
+                |i| i.to_string(),
+            ),
+        };
+        let mut attr: Vec<String> = Vec::with_capacity(NB_ATTR);
         let stype = self.get_door();
         stype.iter().try_for_each(|l| {
             l.iter().try_for_each(|(rs, tdoor)| {
                 attr.clear();
                 if self.outputs.contains(rs) {
-                    attr.push("peripheries=2");
+                    attr.push(String::from("peripheries=2"));
                 }
-                if self.inputs.contains(rs) {
-                    attr.push("shape=diamond");
+                if self.inputs.contains(rs) && !options.initial_arrows {
+                    attr.push(String::from("shape=diamond"));
                 }
-                match tdoor {
-                    DoorType::Both => attr.push("color=purple"),
-                    DoorType::In => attr.push("color=red"),
-                    DoorType::Out => attr.push("color=blue"),
-                    DoorType::None => {}
+                if let Some(colors) = &options.doors {
+                    match tdoor {
+                        DoorType::Both => attr.push(format!("color={}", quote(&colors.both))),
+                        DoorType::In => attr.push(format!("color={}", quote(&colors.input))),
+                        DoorType::Out => attr.push(format!("color={}", quote(&colors.output))),
+                        DoorType::None => {}
+                    }
+                }
+                if options.highlight_states.contains(rs.as_ref().get_value()) {
+                    attr.push(format!(
+                        "style=filled fillcolor={}",
+                        quote(&options.highlight_color)
+                    ));
                 }
                 write!(
                     f,
-                    "\t{} [label = \"{}\" {}]\n",
-                    rs.as_ptr() as usize,
-                    rs.as_ref().get_value(),
+                    "\t{} [label = {} {}]\n",
+                    id(rs),
+                    quote(&rs.as_ref().get_value().to_string()),
                     attr.join(" ")
                 )
             })
         })?;
-        stype.iter().enumerate().try_for_each(|(ind, sub)| {
-            write!(f, "\tsubgraph cluster{} {{\n", ind)?;
-            sub.into_iter()
-                .try_for_each(|(s, _)| write!(f, "\t\t{}\n", s.as_ptr() as usize))?;
-            write!(f, "\t}}\n")
-        })?;
-        self.states.iter().try_for_each(|from| {
-            from.as_ref().get_follows().try_for_each(|(symbol, set)| {
-                set.into_iter().try_for_each(|to| {
-                    if self.states.contains(to) {
-                        write!(
-                            f,
-                            "\t{} -> {} [label = \"{}\"]\n",
-                            from.as_ptr() as usize,
-                            to.as_ptr() as usize,
-                            symbol
-                        )
-                    } else {
-                        Ok(())
-                    }
-                })
+        if options.initial_arrows {
+            self.inputs().enumerate().try_for_each(|(ind, rs)| {
+                write!(
+                    f,
+                    "\t__start{} [shape=point]\n\t__start{} -> {}\n",
+                    ind,
+                    ind,
+                    id(rs)
+                )
+            })?;
+        }
+        if options.clusters {
+            stype.iter().enumerate().try_for_each(|(ind, sub)| {
+                write!(f, "\tsubgraph cluster{} {{\n", ind)?;
+                sub.iter()
+                    .try_for_each(|(s, _)| write!(f, "\t\t{}\n", id(s)))?;
+                write!(f, "\t}}\n")
+            })?;
+        }
+        let highlighted = |from: &RefState<'a, T, V>, symbol: &T, to: &RefState<'a, T, V>| {
+            options.highlight_edges.iter().any(|(f, s, t)| {
+                f == from.as_ref().get_value() && s == symbol && t == to.as_ref().get_value()
             })
-        })?;
+        };
+        self.edges(options.merge_edges)
+            .into_iter()
+            .try_for_each(|(from, symbols, to)| {
+                let extra = match symbols.iter().any(|s| highlighted(from, s, to)) {
+                    true => format!(" color={} penwidth=2", quote(&options.highlight_color)),
+                    false => String::new(),
+                };
+                let label: Vec<String> = symbols.iter().map(|s| s.to_string()).collect();
+                write!(
+                    f,
+                    "\t{} -> {} [label = {}{}]\n",
+                    id(from),
+                    id(to),
+                    quote(&label.join(",")),
+                    extra
+                )
+            })?;
         write!(f, "}}\n")?;
         Ok(f)
     }
 }
+
+/// Returns "s" where double quotes and backslashes are escaped
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Returns "s" as a quoted DOT identifier
+fn quote(s: &str) -> String {
+    format!("\"{}\"", escape(s))
+}
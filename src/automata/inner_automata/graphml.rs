@@ -0,0 +1,104 @@
+//! Module containing the implementation of the GraphML representation of an
+//! automaton, intended for graph analysis tools
+
+use super::{door::DoorType, InnerAutomata};
+use crate::automata::formats::xml::escape;
+use std::{
+    fmt::{Display, Write},
+    hash::Hash,
+};
+
+impl<'a, T, V> InnerAutomata<'a, T, V>
+where
+    T: Eq + Hash + Clone + Display,
+    V: Display,
+{
+    /// Returns the GraphML representation of the automaton. Each node carries
+    /// its label, whether it is initial or final, the index of its strongly
+    /// connected component and its [DoorType] in it, and each edge carries a
+    /// single symbol
+    pub fn to_graphml(&self) -> Result<String, std::fmt::Error> {
+        let mut scc: Vec<(usize, DoorType)> = vec![(0, DoorType::None); self.states.len()];
+        self.get_door()
+            .into_iter()
+            .enumerate()
+            .for_each(|(ind, l)| {
+                l.into_iter().for_each(|(rs, t)| {
+                    if let Some(i) = self.states.get_index_of(&rs) {
+                        scc[i] = (ind, t);
+                    }
+                })
+            });
+        let mut f = String::new();
+        writeln!(f, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(
+            f,
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">"
+        )?;
+        [
+            ("label", "node", "string"),
+            ("initial", "node", "boolean"),
+            ("final", "node", "boolean"),
+            ("scc", "node", "int"),
+            ("door", "node", "string"),
+            ("symbol", "edge", "string"),
+        ]
+        .into_iter()
+        .try_for_each(|(id, target, ty)| {
+            writeln!(
+                f,
+                "\t<key id=\"{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>",
+                id, target, id, ty
+            )
+        })?;
+        writeln!(f, "\t<graph id=\"automaton\" edgedefault=\"directed\">")?;
+        self.states.iter().enumerate().try_for_each(|(i, rs)| {
+            let (ind, door) = &scc[i];
+            let door = match door {
+                DoorType::In => "in",
+                DoorType::Out => "out",
+                DoorType::Both => "both",
+                DoorType::None => "none",
+            };
+            writeln!(f, "\t\t<node id=\"n{}\">", i)?;
+            writeln!(
+                f,
+                "\t\t\t<data key=\"label\">{}</data>",
+                escape(&rs.as_ref().get_value().to_string())
+            )?;
+            writeln!(
+                f,
+                "\t\t\t<data key=\"initial\">{}</data>",
+                self.inputs.contains(rs)
+            )?;
+            writeln!(
+                f,
+                "\t\t\t<data key=\"final\">{}</data>",
+                self.outputs.contains(rs)
+            )?;
+            writeln!(f, "\t\t\t<data key=\"scc\">{}</data>", ind)?;
+            writeln!(f, "\t\t\t<data key=\"door\">{}</data>", door)?;
+            writeln!(f, "\t\t</node>")
+        })?;
+        self.edges(false)
+            .into_iter()
+            .try_for_each(|(from, symbols, to)| {
+                writeln!(
+                    f,
+                    "\t\t<edge source=\"n{}\" target=\"n{}\">",
+                    self.states.get_index_of(from).unwrap_or_default(),
+                    self.states.get_index_of(to).unwrap_or_default()
+                )?;
+                symbols.iter().try_for_each(|s| {
+                    writeln!(
+                        f,
+                        "\t\t\t<data key=\"symbol\">{}</data>",
+                        escape(&s.to_string())
+                    )
+                })?;
+                writeln!(f, "\t\t</edge>")
+            })?;
+        writeln!(f, "\t</graph>\n</graphml>")?;
+        Ok(f)
+    }
+}
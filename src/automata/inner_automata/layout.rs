@@ -0,0 +1,151 @@
+//! Module for the layered layout of an automaton, used by the graphical
+//! representations that place the states themselves
+
+use super::InnerAutomata;
+use std::{collections::VecDeque, hash::Hash};
+
+/// Number of sweeps of the barycenter heuristic ordering the layers
+const NB_SWEEP: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Layered layout of the states of an automaton, where states are given by
+/// their position in the automaton. Every transition that is not a back edge
+/// goes from a layer to the next one
+pub struct Layout {
+    /// States of each layer, in drawing order
+    pub layers: Vec<Vec<usize>>,
+    /// Layer and rank in its layer of each state
+    pub positions: Vec<(usize, usize)>,
+}
+
+impl Layout {
+    /// Returns whether the transition from "from" to "to" goes back to a layer
+    /// lower or equal to the one of "from", loops included
+    pub fn is_back_edge(&self, from: usize, to: usize) -> bool {
+        self.positions[to].0 <= self.positions[from].0
+    }
+}
+
+impl<'a, T, V> InnerAutomata<'a, T, V>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Returns the successors of each state, given by their position in the
+    /// automaton, without loops nor duplicates
+    pub(crate) fn successors(&self) -> Vec<Vec<usize>> {
+        self.states
+            .iter()
+            .enumerate()
+            .map(|(from, rs)| {
+                let mut succ: Vec<usize> = Vec::new();
+                rs.as_ref().get_follows().for_each(|(_, set)| {
+                    set.iter()
+                        .filter_map(|to| self.states.get_index_of(to))
+                        .filter(|to| *to != from)
+                        .for_each(|to| {
+                            if !succ.contains(&to) {
+                                succ.push(to);
+                            }
+                        })
+                });
+                succ
+            })
+            .collect()
+    }
+
+    /// Returns a layered layout of the automaton. Each state lies on the
+    /// layer of its distance from the inputs, so that a transition either goes
+    /// to the next layer or is a back edge, and each layer is then ordered by
+    /// the barycenter heuristic to reduce crossings. States that are not
+    /// accessible are laid out the same way from the first of them
+    pub fn layout(&self) -> Layout {
+        let succ = self.successors();
+        let n = succ.len();
+        let mut layer: Vec<Option<usize>> = vec![None; n];
+        let mut queue: VecDeque<usize> = self
+            .inputs
+            .iter()
+            .filter_map(|rs| self.states.get_index_of(rs))
+            .collect();
+        queue.iter().for_each(|u| layer[*u] = Some(0));
+        let mut next = 0;
+        loop {
+            while let Some(u) = queue.pop_front() {
+                succ[u].iter().for_each(|v| {
+                    if layer[*v].is_none() {
+                        layer[*v] = layer[u].map(|l| l + 1);
+                        queue.push_back(*v);
+                    }
+                })
+            }
+            match (next..n).find(|u| layer[*u].is_none()) {
+                None => break,
+                Some(u) => {
+                    layer[u] = Some(0);
+                    queue.push_back(u);
+                    next = u + 1;
+                }
+            }
+        }
+        let layer: Vec<usize> = layer.into_iter().map(|l| l.unwrap_or(0)).collect();
+        let forward: Vec<Vec<usize>> = succ
+            .iter()
+            .enumerate()
+            .map(|(u, l)| {
+                l.iter()
+                    .filter(|v| layer[**v] == layer[u] + 1)
+                    .copied()
+                    .collect()
+            })
+            .collect();
+        let height = layer.iter().max().map_or(0, |m| m + 1);
+        let mut layers: Vec<Vec<usize>> = vec![Vec::new(); height];
+        (0..n).for_each(|u| layers[layer[u]].push(u));
+        let mut backward: Vec<Vec<usize>> = vec![Vec::new(); n];
+        forward
+            .iter()
+            .enumerate()
+            .for_each(|(u, l)| l.iter().for_each(|v| backward[*v].push(u)));
+        let mut rank = vec![0; n];
+        let rerank = |layers: &Vec<Vec<usize>>, rank: &mut Vec<usize>| {
+            layers
+                .iter()
+                .for_each(|l| l.iter().enumerate().for_each(|(r, u)| rank[*u] = r))
+        };
+        rerank(&layers, &mut rank);
+        for _ in 0..NB_SWEEP {
+            for l in 1..height {
+                sort_layer(&mut layers[l], &backward, &rank);
+                rerank(&layers, &mut rank);
+            }
+            for l in (0..height.saturating_sub(1)).rev() {
+                sort_layer(&mut layers[l], &forward, &rank);
+                rerank(&layers, &mut rank);
+            }
+        }
+        Layout {
+            positions: (0..n).map(|u| (layer[u], rank[u])).collect(),
+            layers,
+        }
+    }
+}
+
+/// Sorts "states" by the mean rank of their "neighbours" on an adjacent layer,
+/// states without neighbours keeping their rank
+fn sort_layer(states: &mut [usize], neighbours: &[Vec<usize>], rank: &[usize]) {
+    let mut keyed: Vec<(f64, usize)> = states
+        .iter()
+        .map(|u| {
+            let adj = &neighbours[*u];
+            match adj.len() {
+                0 => (rank[*u] as f64, *u),
+                len => (
+                    adj.iter().map(|v| rank[*v]).sum::<usize>() as f64 / len as f64,
+                    *u,
+                ),
+            }
+        })
+        .collect();
+    keyed.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+    states.iter_mut().zip(keyed).for_each(|(s, (_, u))| *s = u);
+}
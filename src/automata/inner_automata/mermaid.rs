@@ -0,0 +1,69 @@
+//! Module containing the implementation of the Mermaid display of an
+//! automaton, as a state diagram
+
+use super::InnerAutomata;
+use std::{
+    fmt::{Display, Write},
+    hash::Hash,
+};
+
+impl<'a, T, V> InnerAutomata<'a, T, V>
+where
+    T: Eq + Hash + Clone + Display,
+    V: Display,
+{
+    /// Returns the Mermaid representation of the automaton, a
+    /// ```stateDiagram-v2``` where the transitions between two same states are
+    /// merged
+    pub fn to_mermaid(&self) -> Result<String, std::fmt::Error> {
+        let mut f = String::new();
+        writeln!(f, "stateDiagram-v2\n\tdirection LR")?;
+        self.states.iter().enumerate().try_for_each(|(i, rs)| {
+            writeln!(
+                f,
+                "\ts{} : {}",
+                i,
+                escape(&rs.as_ref().get_value().to_string())
+            )
+        })?;
+        self.inputs
+            .iter()
+            .filter_map(|rs| self.states.get_index_of(rs))
+            .try_for_each(|i| writeln!(f, "\t[*] --> s{}", i))?;
+        self.edges(true)
+            .into_iter()
+            .try_for_each(|(from, symbols, to)| {
+                let label: Vec<String> = symbols.iter().map(|s| escape(&s.to_string())).collect();
+                writeln!(
+                    f,
+                    "\ts{} --> s{} : {}",
+                    self.states.get_index_of(from).unwrap_or_default(),
+                    self.states.get_index_of(to).unwrap_or_default(),
+                    label.join(", ")
+                )
+            })?;
+        self.outputs
+            .iter()
+            .filter_map(|rs| self.states.get_index_of(rs))
+            .try_for_each(|i| writeln!(f, "\ts{} --> [*]", i))?;
+        Ok(f)
+    }
+}
+
+/// Returns "s" where the characters that end a Mermaid label are replaced by
+/// their entity code
+fn escape(s: &str) -> String {
+    s.chars()
+        .fold(String::with_capacity(s.len()), |mut acc, c| {
+            match c {
+                '#' => acc.push_str("#35;"),
+                ';' => acc.push_str("#59;"),
+                ':' => acc.push_str("#58;"),
+                '<' => acc.push_str("#lt;"),
+                '>' => acc.push_str("#gt;"),
+                '"' => acc.push_str("#quot;"),
+                c => acc.push(c),
+            }
+            acc
+        })
+}
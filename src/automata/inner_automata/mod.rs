@@ -1,28 +1,42 @@
 //! Non-secure internal module for automata management
 #![allow(dead_code)]
 
+pub mod ambiguity;
+pub mod bkw;
+pub mod dense;
 pub mod dfs;
 pub mod door;
 pub mod dot;
+pub mod graphml;
+pub mod layout;
+pub mod mermaid;
 pub mod prop;
+pub mod run;
+pub mod rust;
 pub mod scc;
+pub mod search;
 pub mod serde;
 pub mod state;
+pub mod svg;
+pub mod tikz;
 pub mod transform;
 pub mod utils;
 
+use indexmap::IndexSet;
 use state::RefState;
-use std::{collections::HashSet, hash::Hash};
+use std::hash::Hash;
 
-/// Internal data structure for automaton management
+/// Internal data structure for automaton management. States and transitions
+/// are kept in insertion order, so that an automaton built the same way is
+/// always displayed and serialized the same way
 #[derive(Debug)]
 pub struct InnerAutomata<'a, T, V>
 where
     T: Eq + Hash + Clone,
 {
-    states: HashSet<RefState<'a, T, V>>,
-    inputs: HashSet<RefState<'a, T, V>>,
-    outputs: HashSet<RefState<'a, T, V>>,
+    states: IndexSet<RefState<'a, T, V>>,
+    inputs: IndexSet<RefState<'a, T, V>>,
+    outputs: IndexSet<RefState<'a, T, V>>,
 }
 
 impl<'a, T, V> Default for InnerAutomata<'a, T, V>
@@ -46,8 +60,8 @@ where
     fn clone(&self) -> Self {
         let mut auto = Self {
             states: self.states.iter().map(|rs| rs.cloned()).collect(),
-            inputs: HashSet::with_capacity(self.inputs.len()),
-            outputs: HashSet::with_capacity(self.outputs.len()),
+            inputs: IndexSet::with_capacity(self.inputs.len()),
+            outputs: IndexSet::with_capacity(self.outputs.len()),
         };
         self.inputs.iter().for_each(|rs| unsafe {
             auto.inputs
@@ -82,9 +96,9 @@ where
     /// Return the automate composed of "state"" states with "inputs"" and
     /// "outputs" as inputs and outputs, respectively
     pub fn create(
-        states: HashSet<RefState<'a, T, V>>,
-        inputs: HashSet<RefState<'a, T, V>>,
-        outputs: HashSet<RefState<'a, T, V>>,
+        states: IndexSet<RefState<'a, T, V>>,
+        inputs: IndexSet<RefState<'a, T, V>>,
+        outputs: IndexSet<RefState<'a, T, V>>,
     ) -> Self {
         Self {
             states,
@@ -118,19 +132,19 @@ where
         self.outputs.contains(value)
     }
 
-    /// An iterator visiting all states in arbitrary order. The iterator
+    /// An iterator visiting all states in insertion order. The iterator
     /// element type is ``&RefState<T, V>``
     pub fn states(&self) -> impl Iterator<Item = &RefState<'a, T, V>> {
         self.states.iter()
     }
 
-    /// An iterator visiting all inputs in arbitrary order. The iterator
+    /// An iterator visiting all inputs in insertion order. The iterator
     /// element type is ``&RefState<T, V>``
     pub fn inputs(&self) -> impl Iterator<Item = &RefState<'a, T, V>> {
         self.inputs.iter()
     }
 
-    /// An iterator visiting all outputs in arbitrary order. The iterator
+    /// An iterator visiting all outputs in insertion order. The iterator
     /// element type is ``&RefState<T, V>``
     pub fn outputs(&self) -> impl Iterator<Item = &RefState<'a, T, V>> {
         self.outputs.iter()
@@ -178,19 +192,19 @@ where
     /// Removes a state from the set of states. Returns whether the state was
     /// present in the set.
     pub fn remove_state(&mut self, value: &RefState<'a, T, V>) -> bool {
-        self.states.remove(value)
+        self.states.shift_remove(value)
     }
 
     /// Removes a input from the set of states. Returns whether the input was
     /// present in the set.
     pub fn remove_input(&mut self, value: &RefState<'a, T, V>) -> bool {
-        self.inputs.remove(value)
+        self.inputs.shift_remove(value)
     }
 
     /// Removes a output from the set of states. Returns whether the output was
     /// present in the set.
     pub fn remove_output(&mut self, value: &RefState<'a, T, V>) -> bool {
-        self.outputs.remove(value)
+        self.outputs.shift_remove(value)
     }
 
     /// Transform the automaton into its mirror
@@ -198,8 +212,7 @@ where
         std::mem::swap(&mut self.inputs, &mut self.outputs);
         self.states = self
             .states
-            .drain()
-            .into_iter()
+            .drain(..)
             .map(|rs| {
                 rs.reverse();
                 rs
@@ -132,7 +132,7 @@ where
             output
                 .as_ref()
                 .get_follows()
-                .any(|(_, set)| set.intersection(&inp).count() != 0)
+                .any(|(_, set)| set.iter().any(|rs| inp.contains(rs)))
         })
     }
 
@@ -168,7 +168,7 @@ where
             output
                 .as_ref()
                 .get_follows()
-                .any(|(_, set)| set.intersection(&inp).count() != 0)
+                .any(|(_, set)| set.iter().any(|rs| inp.contains(rs)))
         }) {
             return false;
         }
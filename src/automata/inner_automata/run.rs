@@ -0,0 +1,133 @@
+//! Module containing all the information and implementation required for the
+//! tracing of a run of the automaton on a word
+
+use super::{state::RefState, InnerAutomata};
+use std::{collections::HashSet, hash::Hash};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Structure grouping together the information of the run of the automaton on
+/// a word
+pub struct Run<T, V> {
+    /// Sequence defined by steps\[i\] contains the active states after reading
+    /// the first i symbols of the word. The sequence stops at the first empty
+    /// set of states
+    pub steps: Vec<Vec<V>>,
+    /// Result of the run
+    pub outcome: Outcome<T, V>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Result of the run of the automaton on a word
+pub enum Outcome<T, V> {
+    /// The word is accepted
+    Accepted {
+        /// Accepting path, from an input to an output, with one more element
+        /// than "symbols"
+        path: Vec<V>,
+        /// Labels of the transitions of "path"
+        symbols: Vec<T>,
+    },
+    /// The word is rejected
+    Rejected(Rejection<T>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Structure explaining why a word was rejected
+pub struct Rejection<T> {
+    /// Length of the longest prefix of the word that is accepted, or ```None```
+    /// if no prefix is accepted
+    pub longest_accepted_prefix: Option<usize>,
+    /// Position of the symbol after which the set of active states became
+    /// empty, or ```None``` if the whole word was read
+    pub dead_at: Option<usize>,
+    /// Symbols that would have been allowed at the position of the rejection,
+    /// i.e. before the symbol at "dead_at" or at the end of the word
+    pub allowed: Vec<T>,
+}
+
+impl<'a, T, V> InnerAutomata<'a, T, V>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Returns the information of the run of the automaton on "word"
+    pub fn run<'b>(&self, word: impl Iterator<Item = &'b T>) -> Run<T, RefState<'a, T, V>>
+    where
+        'a: 'b,
+        T: 'b,
+    {
+        let mut symbols: Vec<T> = Vec::new();
+        let mut steps: Vec<Vec<RefState<'a, T, V>>> = vec![self.inputs().cloned().collect()];
+        let mut longest = self.inputs().any(|rs| self.is_output(rs)).then_some(0);
+        for symbol in word {
+            let last = unsafe { steps.last().unwrap_unchecked() };
+            let mut seen: HashSet<&RefState<'a, T, V>> = HashSet::new();
+            let next: Vec<RefState<'a, T, V>> = last
+                .iter()
+                .filter_map(|rs| rs.as_ref().get_follow(symbol))
+                .flatten()
+                .filter(|rs| seen.insert(rs))
+                .cloned()
+                .collect();
+            let dead = next.is_empty();
+            symbols.push(symbol.clone());
+            if next.iter().any(|rs| self.is_output(rs)) {
+                longest = Some(symbols.len());
+            }
+            steps.push(next);
+            if dead {
+                let allowed = allowed(&steps[steps.len() - 2]);
+                return Run {
+                    steps,
+                    outcome: Outcome::Rejected(Rejection {
+                        longest_accepted_prefix: longest,
+                        dead_at: Some(symbols.len() - 1),
+                        allowed,
+                    }),
+                };
+            }
+        }
+        let last = unsafe { steps.last().unwrap_unchecked() };
+        let outcome = match last.iter().find(|rs| self.is_output(rs)) {
+            None => Outcome::Rejected(Rejection {
+                longest_accepted_prefix: longest,
+                dead_at: None,
+                allowed: allowed(last),
+            }),
+            Some(end) => {
+                let mut path = vec![end.clone()];
+                for (i, symbol) in symbols.iter().enumerate().rev() {
+                    let cur = unsafe { path.last().unwrap_unchecked() };
+                    let from = unsafe {
+                        steps[i]
+                            .iter()
+                            .find(|rs| {
+                                rs.as_ref()
+                                    .get_follow(symbol)
+                                    .is_some_and(|mut it| it.any(|to| to == cur))
+                            })
+                            .unwrap_unchecked()
+                    };
+                    path.push(from.clone());
+                }
+                path.reverse();
+                Outcome::Accepted { path, symbols }
+            }
+        };
+        Run { steps, outcome }
+    }
+}
+
+/// Returns the symbols labelling the transitions leaving "states", without
+/// duplicates
+fn allowed<'a, T, V>(states: &[RefState<'a, T, V>]) -> Vec<T>
+where
+    T: Eq + Hash + Clone,
+{
+    let mut seen: HashSet<&T> = HashSet::new();
+    states
+        .iter()
+        .flat_map(|rs| rs.as_ref().get_follows().map(|(symbol, _)| symbol))
+        .filter(|symbol| seen.insert(symbol))
+        .cloned()
+        .collect()
+}
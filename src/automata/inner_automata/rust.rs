@@ -0,0 +1,128 @@
+//! Module for generating the Rust source code of a function recognizing the
+//! language of an automaton, intended to be called from a build script
+
+use super::{dense::DenseDfa, InnerAutomata};
+use crate::automata::{Automata, AutomataError, Result, ToRust};
+use crate::regexp::RegExp;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+};
+
+/// Keywords of Rust, which cannot be the name of a function
+const KEYWORDS: &[&str] = &[
+    "_", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Keywords that cannot be written as raw identifiers
+const NOT_RAW: &[&str] = &["_", "crate", "self", "Self", "super"];
+
+/// Returns whether "name" is an identifier that is not a keyword, raw
+/// identifiers such as ```r#match``` included
+fn is_identifier(name: &str) -> bool {
+    let (name, raw) = match name.strip_prefix("r#") {
+        Some(name) => (name, true),
+        None => (name, false),
+    };
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c == '_' || unicode_ident::is_xid_start(c))
+        && chars.all(unicode_ident::is_xid_continue)
+        && !(if raw { NOT_RAW } else { KEYWORDS }).contains(&name)
+}
+
+impl DenseDfa<char> {
+    /// Returns the source code of a function named "name", of signature
+    /// ```fn(&str) -> bool```, returning whether its argument is recognized.
+    /// The function is a ```match``` over the states and symbols and does not
+    /// depend on this crate. Returns an error if "name" is not an identifier
+    /// or is a keyword
+    pub fn to_rust(&self, name: &str) -> Result<String> {
+        if !is_identifier(name) {
+            return Err(AutomataError::InvalidIdentifier(String::from(name)));
+        }
+        let mut f = String::new();
+        self.write_rust(&mut f, name)
+            .expect("writing in a String does not fail");
+        Ok(f)
+    }
+
+    /// Writes in "f" the source code of the function named "name", see
+    /// [Self::to_rust]
+    fn write_rust(&self, f: &mut String, name: &str) -> fmt::Result {
+        writeln!(f, "/// Returns whether \"word\" is recognized")?;
+        writeln!(f, "/// Generated by glushkovizer, do not edit")?;
+        // Without transition, the match would only have its default arm and
+        // the loop would be unreachable code after its first iteration
+        let stuck = self.table().iter().all(|&to| to == Self::DEAD);
+        if !self.finals().contains(&true) || (stuck && self.finals().first() != Some(&true)) {
+            writeln!(f, "pub fn {}(_word: &str) -> bool {{\n    false\n}}", name)?;
+            return Ok(());
+        }
+        if stuck {
+            writeln!(
+                f,
+                "pub fn {}(word: &str) -> bool {{\n    word.is_empty()\n}}",
+                name
+            )?;
+            return Ok(());
+        }
+        writeln!(f, "pub fn {}(word: &str) -> bool {{", name)?;
+        writeln!(f, "    let mut state: u32 = 0;")?;
+        writeln!(f, "    for symbol in word.chars() {{")?;
+        writeln!(f, "        state = match (state, symbol) {{")?;
+        for (from, row) in self.table().chunks(self.symbols().len().max(1)).enumerate() {
+            let mut targets: BTreeMap<u32, Vec<char>> = BTreeMap::new();
+            row.iter()
+                .zip(self.symbols())
+                .filter(|(&to, _)| to != Self::DEAD)
+                .for_each(|(&to, &symbol)| targets.entry(to).or_default().push(symbol));
+            for (to, symbols) in targets {
+                let symbols: Vec<String> = symbols.iter().map(|c| format!("{:?}", c)).collect();
+                writeln!(
+                    f,
+                    "            ({}, {}) => {},",
+                    from,
+                    symbols.join(" | "),
+                    to
+                )?;
+            }
+        }
+        writeln!(f, "            _ => return false,")?;
+        writeln!(f, "        }};")?;
+        writeln!(f, "    }}")?;
+        let finals: Vec<String> = self
+            .finals()
+            .iter()
+            .enumerate()
+            .filter(|(_, &is_final)| is_final)
+            .map(|(state, _)| state.to_string())
+            .collect();
+        writeln!(f, "    matches!(state, {})", finals.join(" | "))?;
+        writeln!(f, "}}")
+    }
+}
+
+impl<'a, V> InnerAutomata<'a, char, V>
+where
+    V: Eq + Clone,
+{
+    /// Returns the source code of a function named "name" recognizing the
+    /// language of the automaton, see [DenseDfa::to_rust]
+    pub fn to_rust(&self, name: &str) -> Result<String> {
+        self.compile().to_rust(name)
+    }
+}
+
+impl RegExp<char> {
+    /// Returns the source code of a function named "name" recognizing the
+    /// language of the regular expression, see [DenseDfa::to_rust]
+    pub fn to_rust(&self, name: &str) -> Result<String> {
+        Automata::from(self.clone()).to_rust(name)
+    }
+}
@@ -0,0 +1,104 @@
+//! Module for finding the occurrences of the language of an automaton inside a
+//! text
+
+use super::{state::RefState, InnerAutomata};
+use std::{collections::HashSet, hash::Hash, ops::Range};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+/// Enumeration of the ways of reporting occurrences in a text
+pub enum SearchMode {
+    /// Reports, from left to right, the longest occurrence starting at the
+    /// leftmost position, then resumes the search after it. Occurrences do not
+    /// overlap
+    #[default]
+    LeftmostLongest,
+    /// Reports every factor of the text that is recognized, so occurrences may
+    /// overlap
+    All,
+}
+
+impl<'a, T, V> InnerAutomata<'a, T, V>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Returns the ranges of the occurrences of the language of the automaton
+    /// in "text", sorted by start then by end. The empty word is reported as
+    /// an empty range when it is recognized
+    pub fn find_all(&self, text: &[T], mode: SearchMode) -> Vec<Range<usize>> {
+        match mode {
+            SearchMode::LeftmostLongest => self.find_leftmost_longest(text),
+            SearchMode::All => self.find_every(text),
+        }
+    }
+
+    /// Returns the leftmost longest occurrence of the language of the
+    /// automaton in "text", stopping at the first one
+    pub fn find(&self, text: &[T]) -> Option<Range<usize>> {
+        (0..=text.len()).find_map(|start| self.longest_from(text, start).map(|end| start..end))
+    }
+
+    /// Returns the end of the longest occurrence starting at "start" in "text"
+    fn longest_from(&self, text: &[T], start: usize) -> Option<usize> {
+        let mut current: HashSet<&RefState<'a, T, V>> = self.inputs().collect();
+        let mut end = current.iter().any(|rs| self.is_output(rs)).then_some(start);
+        for (i, symbol) in text.iter().enumerate().skip(start) {
+            current = current
+                .into_iter()
+                .filter_map(|rs| rs.as_ref().get_follow(symbol))
+                .flatten()
+                .collect();
+            if current.is_empty() {
+                break;
+            }
+            if current.iter().any(|rs| self.is_output(rs)) {
+                end = Some(i + 1);
+            }
+        }
+        end
+    }
+
+    /// Auxiliary function of [Self::find_all] for [SearchMode::LeftmostLongest]
+    fn find_leftmost_longest(&self, text: &[T]) -> Vec<Range<usize>> {
+        let mut res = Vec::new();
+        let mut start = 0;
+        while start <= text.len() {
+            match self.longest_from(text, start) {
+                Some(end) => {
+                    res.push(start..end);
+                    start = if end > start { end } else { start + 1 };
+                }
+                None => start += 1,
+            }
+        }
+        res
+    }
+
+    /// Auxiliary function of [Self::find_all] for [SearchMode::All], simulating the
+    /// automaton with a loop on its inputs, each active state remembering the
+    /// positions where its occurrences started
+    fn find_every(&self, text: &[T]) -> Vec<Range<usize>> {
+        let mut res = Vec::new();
+        let mut current: HashSet<(&RefState<'a, T, V>, usize)> = HashSet::new();
+        for i in 0..=text.len() {
+            current.extend(self.inputs().map(|rs| (rs, i)));
+            current
+                .iter()
+                .filter(|(rs, _)| self.is_output(rs))
+                .for_each(|(_, start)| res.push(*start..i));
+            if let Some(symbol) = text.get(i) {
+                current = current
+                    .into_iter()
+                    .filter_map(|(rs, start)| {
+                        rs.as_ref()
+                            .get_follow(symbol)
+                            .map(|it| it.map(move |to| (to, start)))
+                    })
+                    .flatten()
+                    .collect();
+            }
+        }
+        res.sort_by_key(|r| (r.start, r.end));
+        res.dedup();
+        res
+    }
+}
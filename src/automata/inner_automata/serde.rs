@@ -1,14 +1,36 @@
+//! Module for the serialization of automata. The format is versioned by the
+//! field ```version```, written last so that the positional layout of the
+//! files written before it existed is a prefix of the current one. These
+//! files, which have no such field, are read as the version ```0```. Only
+//! self-describing formats, such as JSON or CBOR, mark where a sequence ends
+//! and can read them: a format with a fixed layout, such as bincode, expects
+//! the version and fails at the end of the input. The format is described by
+//! the JSON Schema [JSON_SCHEMA]
+//!
+//! Reading an automaton requires its symbols and its state values to
+//! implement [Debug], to name the offending value in the errors. Since values
+//! are only compared for equality, the duplicate states and the states of
+//! the transitions are found in a time quadratic in the number of states,
+//! ```O(n² + n·e)``` for ```n``` states and ```e``` transitions
+
 use crate::automata::inner_automata::state::RefState;
 
 use super::InnerAutomata;
+use indexmap::IndexSet;
 use serde::de::{self, MapAccess, SeqAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::collections::HashSet;
-use std::fmt;
+use std::fmt::{self, Debug};
 use std::hash::Hash;
 use std::marker::PhantomData;
 
+/// Version of the serialization format written by this library
+pub const FORMAT_VERSION: u32 = 1;
+
+/// JSON Schema of the serialization format, for automata as well as for
+/// documents
+pub const JSON_SCHEMA: &str = include_str!("../../../schema/automata.schema.json");
+
 impl<'a, T, V> Serialize for InnerAutomata<'a, T, V>
 where
     T: Serialize + Eq + Hash + Clone,
@@ -18,7 +40,7 @@ where
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Automata", 4)?;
+        let mut state = serializer.serialize_struct("Automata", 5)?;
         state.serialize_field(
             "states",
             &self
@@ -63,14 +85,24 @@ where
                     acc
                 }),
         )?;
+        state.serialize_field("version", &FORMAT_VERSION)?;
         state.end()
     }
 }
 
-impl<'de, 'a, T, V> Deserialize<'de> for InnerAutomata<'a, T, V>
+/// Unchecked fields of an automaton, used for deserialization
+struct RawAutomata<T, V> {
+    version: u32,
+    states: Vec<V>,
+    inputs: Vec<V>,
+    outputs: Vec<V>,
+    follows: Vec<(V, T, V)>,
+}
+
+impl<'de, T, V> Deserialize<'de> for RawAutomata<T, V>
 where
-    T: Deserialize<'de> + Eq + Hash + Clone + 'a,
-    V: Deserialize<'de> + Eq + 'a,
+    T: Deserialize<'de>,
+    V: Deserialize<'de>,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -83,187 +115,205 @@ where
             Inputs,
             Outputs,
             Follows,
+            Version,
+            #[serde(other)]
+            Other,
         }
 
-        struct InnerAutomataVisitor<'a, T, V>
-        where
-            T: Eq + Hash + Clone,
-            V: Eq,
-        {
-            phantom: PhantomData<&'a (T, V)>,
+        struct RawAutomataVisitor<T, V> {
+            phantom: PhantomData<(T, V)>,
         }
 
-        impl<'de, 'a, T, V> Visitor<'de> for InnerAutomataVisitor<'a, T, V>
+        impl<'de, T, V> Visitor<'de> for RawAutomataVisitor<T, V>
         where
-            T: Deserialize<'de> + Eq + Hash + Clone,
-            V: Deserialize<'de> + Eq,
+            T: Deserialize<'de>,
+            V: Deserialize<'de>,
         {
-            type Value = InnerAutomata<'a, T, V>;
+            type Value = RawAutomata<T, V>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 formatter.write_str("struct Automata")
             }
 
-            fn visit_seq<W>(self, mut seq: W) -> Result<InnerAutomata<'a, T, V>, W::Error>
+            /// Reads the positional layout, where the version is the last
+            /// element and is missing in the layout of the version 0
+            fn visit_seq<W>(self, mut seq: W) -> Result<RawAutomata<T, V>, W::Error>
             where
                 W: SeqAccess<'de>,
             {
-                let states: Vec<V> = seq
+                let states = seq
                     .next_element()?
                     .ok_or_else(|| de::Error::invalid_length(0, &self))?;
-                let inputs: Vec<V> = seq
+                let inputs = seq
                     .next_element()?
                     .ok_or_else(|| de::Error::invalid_length(1, &self))?;
-                let outputs: Vec<V> = seq
+                let outputs = seq
                     .next_element()?
                     .ok_or_else(|| de::Error::invalid_length(2, &self))?;
-                let follows: Vec<(V, T, V)> = seq
+                let follows = seq
                     .next_element()?
-                    .ok_or_else(|| de::Error::invalid_length(4, &self))?;
-                let states: HashSet<RefState<'a, T, V>> = states
-                    .into_iter()
-                    .map(|state| RefState::new(state))
-                    .collect();
-                let inputs: HashSet<RefState<'a, T, V>> =
-                    inputs
-                        .into_iter()
-                        .try_fold(HashSet::new(), |mut acc, input| {
-                            match states.iter().find(|rs| rs.as_ref().get_value() == &input) {
-                                None => Err(de::Error::custom("input not in states")),
-                                Some(rs) => {
-                                    acc.insert(rs.clone());
-                                    Ok(acc)
-                                }
-                            }
-                        })?;
-                let outputs: HashSet<RefState<'a, T, V>> =
-                    outputs
-                        .into_iter()
-                        .try_fold(HashSet::new(), |mut acc, output| {
-                            match states.iter().find(|rs| rs.as_ref().get_value() == &output) {
-                                None => Err(de::Error::custom("ouput not in states")),
-                                Some(rs) => {
-                                    acc.insert(rs.clone());
-                                    Ok(acc)
-                                }
-                            }
-                        })?;
-                follows.into_iter().try_for_each(|(from, symbol, to)| {
-                    let rto = match states.iter().find(|rs| rs.as_ref().get_value() == &to) {
-                        None => Err(de::Error::custom("Unknown to state")),
-                        Some(rs) => Ok(rs.clone()),
-                    }?;
-                    let rfrom = match states.iter().find(|rs| rs.as_ref().get_value() == &from) {
-                        None => Err(de::Error::custom("Unknown from state")),
-                        Some(rs) => Ok(rs),
-                    }?;
-                    rfrom.add_follow(rto, symbol);
-                    Ok(())
-                })?;
-                Ok(InnerAutomata {
+                    .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+                Ok(RawAutomata {
+                    version: seq.next_element()?.unwrap_or(0),
                     states,
                     inputs,
                     outputs,
+                    follows,
                 })
             }
 
-            fn visit_map<W>(self, mut map: W) -> Result<InnerAutomata<'a, T, V>, W::Error>
+            /// Reads the layout by field names, ignoring the unknown fields so
+            /// that an automaton can be read from a document
+            fn visit_map<W>(self, mut map: W) -> Result<RawAutomata<T, V>, W::Error>
             where
                 W: MapAccess<'de>,
             {
-                let mut field1 = None;
-                let mut field2 = None;
-                let mut field3 = None;
-                let mut field4 = None;
+                let mut version = None;
+                let mut states = None;
+                let mut inputs = None;
+                let mut outputs = None;
+                let mut follows = None;
                 while let Some(key) = map.next_key()? {
                     match key {
+                        Field::Version => {
+                            if version.is_some() {
+                                return Err(de::Error::duplicate_field("version"));
+                            }
+                            version = Some(map.next_value()?);
+                        }
                         Field::States => {
-                            if field1.is_some() {
+                            if states.is_some() {
                                 return Err(de::Error::duplicate_field("states"));
                             }
-                            field1 = Some(map.next_value()?);
+                            states = Some(map.next_value()?);
                         }
                         Field::Inputs => {
-                            if field2.is_some() {
+                            if inputs.is_some() {
                                 return Err(de::Error::duplicate_field("inputs"));
                             }
-                            field2 = Some(map.next_value()?);
+                            inputs = Some(map.next_value()?);
                         }
                         Field::Outputs => {
-                            if field3.is_some() {
-                                return Err(de::Error::duplicate_field("ouputs"));
+                            if outputs.is_some() {
+                                return Err(de::Error::duplicate_field("outputs"));
                             }
-                            field3 = Some(map.next_value()?);
+                            outputs = Some(map.next_value()?);
                         }
                         Field::Follows => {
-                            if field4.is_some() {
+                            if follows.is_some() {
                                 return Err(de::Error::duplicate_field("follows"));
                             }
-                            field4 = Some(map.next_value()?);
+                            follows = Some(map.next_value()?);
+                        }
+                        Field::Other => {
+                            map.next_value::<de::IgnoredAny>()?;
                         }
                     }
                 }
-                let states: Vec<V> = field1.ok_or_else(|| de::Error::missing_field("states"))?;
-                let inputs: Vec<V> = field2.ok_or_else(|| de::Error::missing_field("inputs"))?;
-                let outputs: Vec<V> = field3.ok_or_else(|| de::Error::missing_field("outputs"))?;
-                let follows: Vec<(V, T, V)> =
-                    field4.ok_or_else(|| de::Error::missing_field("follows"))?;
-                let states: HashSet<RefState<'a, T, V>> = states
-                    .into_iter()
-                    .map(|state| RefState::new(state))
-                    .collect();
-                let inputs: HashSet<RefState<'a, T, V>> =
-                    inputs
-                        .into_iter()
-                        .try_fold(HashSet::new(), |mut acc, input| {
-                            match states.iter().find(|rs| rs.as_ref().get_value() == &input) {
-                                None => Err(de::Error::custom("input not in states")),
-                                Some(rs) => {
-                                    acc.insert(rs.clone());
-                                    Ok(acc)
-                                }
-                            }
-                        })?;
-                let outputs: HashSet<RefState<'a, T, V>> =
-                    outputs
-                        .into_iter()
-                        .try_fold(HashSet::new(), |mut acc, output| {
-                            match states.iter().find(|rs| rs.as_ref().get_value() == &output) {
-                                None => Err(de::Error::custom("ouput not in states")),
-                                Some(rs) => {
-                                    acc.insert(rs.clone());
-                                    Ok(acc)
-                                }
-                            }
-                        })?;
-                follows.into_iter().try_for_each(|(from, symbol, to)| {
-                    let rto = match states.iter().find(|rs| rs.as_ref().get_value() == &to) {
-                        None => Err(de::Error::custom("Unknown to state")),
-                        Some(rs) => Ok(rs.clone()),
-                    }?;
-                    let rfrom = match states.iter().find(|rs| rs.as_ref().get_value() == &from) {
-                        None => Err(de::Error::custom("Unknown from state")),
-                        Some(rs) => Ok(rs),
-                    }?;
-                    rfrom.add_follow(rto, symbol);
-                    Ok(())
-                })?;
-                Ok(InnerAutomata {
-                    states,
-                    inputs,
-                    outputs,
+                Ok(RawAutomata {
+                    version: version.unwrap_or(0),
+                    states: states.ok_or_else(|| de::Error::missing_field("states"))?,
+                    inputs: inputs.ok_or_else(|| de::Error::missing_field("inputs"))?,
+                    outputs: outputs.ok_or_else(|| de::Error::missing_field("outputs"))?,
+                    follows: follows.ok_or_else(|| de::Error::missing_field("follows"))?,
                 })
             }
         }
 
-        const FIELDS: &'static [&'static str] = &["states", "inputs", "outputs", "follows"];
+        const FIELDS: &[&str] = &["states", "inputs", "outputs", "follows", "version"];
 
         deserializer.deserialize_struct(
             "Automata",
             FIELDS,
-            InnerAutomataVisitor {
+            RawAutomataVisitor {
                 phantom: PhantomData,
             },
         )
     }
 }
+
+impl<T, V> RawAutomata<T, V> {
+    /// Returns the fields in the layout of [FORMAT_VERSION], or an error if
+    /// the version is unknown
+    fn migrate(self) -> Result<Self, String> {
+        match self.version {
+            // Layout written before the versioning, identical apart from the
+            // version itself
+            0 => Ok(Self {
+                version: FORMAT_VERSION,
+                ..self
+            }),
+            FORMAT_VERSION => Ok(self),
+            v => Err(format!(
+                "unsupported format version {}, the latest known version is {}",
+                v, FORMAT_VERSION
+            )),
+        }
+    }
+
+    /// Returns the automaton described by the fields, or an error naming the
+    /// first value that is duplicated or refers to an unknown state
+    fn build<'a>(self) -> Result<InnerAutomata<'a, T, V>, String>
+    where
+        T: Eq + Hash + Clone + Debug,
+        V: Eq + Debug,
+    {
+        let raw = self.migrate()?;
+        let mut states: IndexSet<RefState<'a, T, V>> = IndexSet::new();
+        for state in raw.states {
+            if states.iter().any(|rs| rs.as_ref().get_value() == &state) {
+                return Err(format!("duplicate state {:?}", state));
+            }
+            states.insert(RefState::new(state));
+        }
+        let find = |value: &V| {
+            states
+                .iter()
+                .find(|rs| rs.as_ref().get_value() == value)
+                .cloned()
+        };
+        let inputs = raw
+            .inputs
+            .iter()
+            .map(|input| find(input).ok_or_else(|| format!("input {:?} not in states", input)))
+            .collect::<Result<IndexSet<_>, _>>()?;
+        let outputs = raw
+            .outputs
+            .iter()
+            .map(|output| find(output).ok_or_else(|| format!("output {:?} not in states", output)))
+            .collect::<Result<IndexSet<_>, _>>()?;
+        for (from, symbol, to) in raw.follows {
+            let unknown = |what: &str, state: &V| {
+                format!(
+                    "unknown {} state {:?} in the transition ({:?}, {:?}, {:?})",
+                    what, state, from, symbol, to
+                )
+            };
+            let rfrom = find(&from).ok_or_else(|| unknown("from", &from))?;
+            let rto = find(&to).ok_or_else(|| unknown("to", &to))?;
+            rfrom.add_follow(rto, symbol);
+        }
+        Ok(InnerAutomata {
+            states,
+            inputs,
+            outputs,
+        })
+    }
+}
+
+/// The values are formatted with [Debug] to name the offending one in the
+/// errors
+impl<'de, 'a, T, V> Deserialize<'de> for InnerAutomata<'a, T, V>
+where
+    T: Deserialize<'de> + Eq + Hash + Clone + Debug + 'a,
+    V: Deserialize<'de> + Eq + Debug + 'a,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        RawAutomata::deserialize(deserializer)?
+            .build()
+            .map_err(de::Error::custom)
+    }
+}
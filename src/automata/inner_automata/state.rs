@@ -1,23 +1,20 @@
 //! Insecure internal module allowing the management of states and their
 //! reference
 
-use std::{
-    collections::{HashMap, HashSet},
-    hash::Hash,
-    pin::Pin,
-};
+use indexmap::{IndexMap, IndexSet};
+use std::{hash::Hash, pin::Pin};
 
-/// Creates a HashSet with the given list of values
+/// Creates an IndexSet with the given list of values
 macro_rules! set {
     [ $x:expr ] => {
         {
-            let mut y = HashSet::new();
+            let mut y = IndexSet::new();
             y.insert($x);
             y
         }
     };
     [ $($x:expr),+ ] => {
-        HashSet::from([ $($x),+ ])
+        IndexSet::from([ $($x),+ ])
     };
 }
 
@@ -109,7 +106,7 @@ where
         Self::new(self.as_ref().value.clone())
     }
 
-    /// Returns the list of symbol and [HashSet] pairs representing the
+    /// Returns the list of symbol and [IndexSet] pairs representing the
     /// follows of this state
     pub fn get_follows(&self) -> Vec<(T, Vec<V>)> {
         self.as_ref()
@@ -124,7 +121,7 @@ where
             .collect()
     }
 
-    /// Returns the list of symbol and [HashSet] pairs representing the
+    /// Returns the list of symbol and [IndexSet] pairs representing the
     /// previous of this state
     pub fn get_previous(&self) -> Vec<(T, Vec<V>)> {
         self.as_ref()
@@ -148,8 +145,8 @@ where
     T: Eq + Hash,
 {
     value: V,
-    previous: HashMap<T, HashSet<RefState<'a, T, V>>>,
-    follow: HashMap<T, HashSet<RefState<'a, T, V>>>,
+    previous: IndexMap<T, IndexSet<RefState<'a, T, V>>>,
+    follow: IndexMap<T, IndexSet<RefState<'a, T, V>>>,
 }
 
 impl<'a, T, V> State<'a, T, V>
@@ -181,12 +178,12 @@ where
     }
 
     /// Returns the set of symbol and follow pairs
-    pub fn get_follows(&self) -> impl Iterator<Item = (&T, &HashSet<RefState<'a, T, V>>)> {
+    pub fn get_follows(&self) -> impl Iterator<Item = (&T, &IndexSet<RefState<'a, T, V>>)> {
         self.follow.iter()
     }
 
     /// Returns the set of symbol and previous pairs
-    pub fn get_previous(&self) -> impl Iterator<Item = (&T, &HashSet<RefState<'a, T, V>>)> {
+    pub fn get_previous(&self) -> impl Iterator<Item = (&T, &IndexSet<RefState<'a, T, V>>)> {
         self.previous.iter()
     }
 
@@ -287,22 +284,22 @@ where
     /// Returns if the transition existed before
     pub fn remove_follow(&self, to: &RefState<'a, T, V>, symbol: &T) -> bool {
         let fmut = self.as_mut();
-        match fmut.follow.get_mut(&symbol) {
+        match fmut.follow.get_mut(symbol) {
             None => {
                 return false;
             }
             Some(set) => {
-                set.remove(to);
+                set.shift_remove(to);
                 if set.is_empty() {
-                    fmut.follow.remove(&symbol);
+                    fmut.follow.shift_remove(symbol);
                 }
             }
         }
         let tmut = to.as_mut();
-        let set = unsafe { tmut.previous.get_mut(&symbol).unwrap_unchecked() };
-        let res = set.remove(self);
+        let set = unsafe { tmut.previous.get_mut(symbol).unwrap_unchecked() };
+        let res = set.shift_remove(self);
         if set.is_empty() {
-            tmut.previous.remove(&symbol);
+            tmut.previous.shift_remove(symbol);
         }
         res
     }
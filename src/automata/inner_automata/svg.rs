@@ -0,0 +1,246 @@
+//! Module containing the implementation of the SVG display of an automaton,
+//! drawn without any external tool
+
+use super::InnerAutomata;
+use crate::automata::formats::xml::escape;
+use std::{
+    fmt::{Display, Write},
+    hash::Hash,
+};
+
+/// Radius of the circle of a state
+const RADIUS: f64 = 20.0;
+/// Distance between two layers
+const LAYER_DISTANCE: f64 = 110.0;
+/// Distance between two states of the same layer
+const STATE_DISTANCE: f64 = 80.0;
+/// Length of the arrow marking an input
+const INITIAL_LENGTH: f64 = 30.0;
+/// Margin around the drawing, the orbits and the labels
+const MARGIN: f64 = 12.0;
+/// Approximate width of a character of a label
+const CHAR_WIDTH: f64 = 7.0;
+/// Height of a label
+const FONT_SIZE: f64 = 14.0;
+
+/// Bounding box of the drawing, extended as elements are drawn
+struct BoundingBox {
+    min: (f64, f64),
+    max: (f64, f64),
+}
+
+impl BoundingBox {
+    /// Extends the box so that it contains the point ("x", "y") with a margin
+    /// of "margin"
+    fn extend(&mut self, x: f64, y: f64, margin: f64) {
+        self.min = (self.min.0.min(x - margin), self.min.1.min(y - margin));
+        self.max = (self.max.0.max(x + margin), self.max.1.max(y + margin));
+    }
+
+    /// Extends the box so that it contains "label" centered on ("x", "y")
+    fn extend_label(&mut self, x: f64, y: f64, label: &str) {
+        let half = label.chars().count() as f64 * CHAR_WIDTH / 2.0;
+        self.extend(x - half, y, FONT_SIZE);
+        self.extend(x + half, y, FONT_SIZE);
+    }
+}
+
+impl<'a, T, V> InnerAutomata<'a, T, V>
+where
+    T: Eq + Hash + Clone + Display,
+    V: Display,
+{
+    /// Returns the SVG representation of the automaton, where the states are
+    /// placed from left to right by [InnerAutomata::layout]. Final states are
+    /// drawn with a double circle, inputs with an incoming arrow, back edges
+    /// and loops are curved and, if "orbits" is ```true```, each orbit is
+    /// surrounded by a dashed frame
+    pub fn to_svg(&self, orbits: bool) -> Result<String, std::fmt::Error> {
+        let layout = self.layout();
+        let center: Vec<(f64, f64)> = layout
+            .positions
+            .iter()
+            .map(|(layer, rank)| {
+                let size = layout.layers[*layer].len() as f64;
+                (
+                    *layer as f64 * LAYER_DISTANCE,
+                    (*rank as f64 - (size - 1.0) / 2.0) * STATE_DISTANCE,
+                )
+            })
+            .collect();
+        let mut bbox = BoundingBox {
+            min: (0.0, 0.0),
+            max: (0.0, 0.0),
+        };
+        let mut body = String::new();
+        if orbits {
+            self.kosaraju()
+                .into_iter()
+                .filter(|scc| {
+                    scc.len() != 1
+                        || scc[0]
+                            .as_ref()
+                            .get_follows()
+                            .any(|(_, set)| set.contains(&scc[0]))
+                })
+                .try_for_each(|scc| {
+                    let mut frame = BoundingBox {
+                        min: (f64::MAX, f64::MAX),
+                        max: (f64::MIN, f64::MIN),
+                    };
+                    scc.iter()
+                        .filter_map(|rs| self.states.get_index_of(rs))
+                        .for_each(|i| frame.extend(center[i].0, center[i].1, RADIUS + MARGIN));
+                    bbox.extend(frame.min.0, frame.min.1, MARGIN);
+                    bbox.extend(frame.max.0, frame.max.1, MARGIN);
+                    writeln!(
+                        body,
+                        "\t<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" \
+                        rx=\"10\" fill=\"none\" stroke=\"gray\" stroke-dasharray=\"4 4\"/>",
+                        frame.min.0,
+                        frame.min.1,
+                        frame.max.0 - frame.min.0,
+                        frame.max.1 - frame.min.1
+                    )
+                })?;
+        }
+        self.states.iter().enumerate().try_for_each(|(i, rs)| {
+            let (x, y) = center[i];
+            bbox.extend(x, y, RADIUS + MARGIN);
+            writeln!(
+                body,
+                "\t<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"white\" stroke=\"black\"/>",
+                x, y, RADIUS
+            )?;
+            if self.outputs.contains(rs) {
+                writeln!(
+                    body,
+                    "\t<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"none\" stroke=\"black\"/>",
+                    x,
+                    y,
+                    RADIUS - 4.0
+                )?;
+            }
+            if self.inputs.contains(rs) {
+                bbox.extend(x - RADIUS - INITIAL_LENGTH, y, MARGIN);
+                writeln!(
+                    body,
+                    "\t<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" \
+                    stroke=\"black\" marker-end=\"url(#arrow)\"/>",
+                    x - RADIUS - INITIAL_LENGTH,
+                    y,
+                    x - RADIUS,
+                    y
+                )?;
+            }
+            let label = escape(&rs.as_ref().get_value().to_string());
+            bbox.extend_label(x, y, &label);
+            writeln!(
+                body,
+                "\t<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" \
+                dominant-baseline=\"central\">{}</text>",
+                x, y, label
+            )
+        })?;
+        self.edges(true)
+            .into_iter()
+            .try_for_each(|(from, symbols, to)| {
+                let (from, to) = (
+                    self.states.get_index_of(from).unwrap_or_default(),
+                    self.states.get_index_of(to).unwrap_or_default(),
+                );
+                let (p, q) = (center[from], center[to]);
+                let label: Vec<String> = symbols.iter().map(|s| escape(&s.to_string())).collect();
+                let label = label.join(", ");
+                let (path, at) = if from == to {
+                    let (sx, sy) = (RADIUS / 2.0, RADIUS * 0.87);
+                    let top = p.1 - RADIUS * 2.6;
+                    bbox.extend(p.0, top, MARGIN);
+                    (
+                        format!(
+                            "M {:.1} {:.1} C {:.1} {:.1} {:.1} {:.1} {:.1} {:.1}",
+                            p.0 - sx,
+                            p.1 - sy,
+                            p.0 - RADIUS,
+                            top,
+                            p.0 + RADIUS,
+                            top,
+                            p.0 + sx,
+                            p.1 - sy
+                        ),
+                        (p.0, top - 4.0),
+                    )
+                } else if !layout.is_back_edge(from, to) {
+                    let (dx, dy, _) = unit(p, q);
+                    let (a, b) = (
+                        (p.0 + RADIUS * dx, p.1 + RADIUS * dy),
+                        (q.0 - RADIUS * dx, q.1 - RADIUS * dy),
+                    );
+                    (
+                        format!("M {:.1} {:.1} L {:.1} {:.1}", a.0, a.1, b.0, b.1),
+                        ((a.0 + b.0) / 2.0 + 10.0 * dy, (a.1 + b.1) / 2.0 - 10.0 * dx),
+                    )
+                } else {
+                    let (dx, dy, len) = unit(p, q);
+                    let bend = len * 0.25 + RADIUS;
+                    let c = ((p.0 + q.0) / 2.0 + bend * dy, (p.1 + q.1) / 2.0 - bend * dx);
+                    bbox.extend(c.0, c.1, MARGIN);
+                    let (ax, ay, _) = unit(p, c);
+                    let (bx, by, _) = unit(q, c);
+                    let (a, b) = (
+                        (p.0 + RADIUS * ax, p.1 + RADIUS * ay),
+                        (q.0 + RADIUS * bx, q.1 + RADIUS * by),
+                    );
+                    (
+                        format!(
+                            "M {:.1} {:.1} Q {:.1} {:.1} {:.1} {:.1}",
+                            a.0, a.1, c.0, c.1, b.0, b.1
+                        ),
+                        (
+                            (a.0 + b.0) / 4.0 + c.0 / 2.0 + 10.0 * dy,
+                            (a.1 + b.1) / 4.0 + c.1 / 2.0 - 10.0 * dx,
+                        ),
+                    )
+                };
+                bbox.extend_label(at.0, at.1, &label);
+                writeln!(
+                    body,
+                    "\t<path d=\"{}\" fill=\"none\" stroke=\"black\" marker-end=\"url(#arrow)\"/>",
+                    path
+                )?;
+                writeln!(
+                    body,
+                    "\t<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" \
+                    dominant-baseline=\"central\">{}</text>",
+                    at.0, at.1, label
+                )
+            })?;
+        let (width, height) = (bbox.max.0 - bbox.min.0, bbox.max.1 - bbox.min.1);
+        let mut f = String::new();
+        writeln!(
+            f,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.1}\" height=\"{:.1}\" \
+            viewBox=\"{:.1} {:.1} {:.1} {:.1}\" font-family=\"sans-serif\" font-size=\"{}\">",
+            width, height, bbox.min.0, bbox.min.1, width, height, FONT_SIZE
+        )?;
+        writeln!(
+            f,
+            "\t<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+            markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\">\
+            <path d=\"M 0 0 L 10 5 L 0 10 z\"/></marker></defs>"
+        )?;
+        f.push_str(&body);
+        writeln!(f, "</svg>")?;
+        Ok(f)
+    }
+}
+
+/// Returns the unit vector going from "p" to "q" and the distance between them
+fn unit(p: (f64, f64), q: (f64, f64)) -> (f64, f64, f64) {
+    let (dx, dy) = (q.0 - p.0, q.1 - p.1);
+    let len = (dx * dx + dy * dy).sqrt();
+    if len == 0.0 {
+        return (1.0, 0.0, 0.0);
+    }
+    (dx / len, dy / len, len)
+}
@@ -0,0 +1,114 @@
+//! Module containing the implementation of the TikZ display of an automaton,
+//! intended for LaTeX documents
+
+use super::{door::DoorType, dot::DoorColors, InnerAutomata};
+use std::{
+    fmt::{Display, Write},
+    hash::Hash,
+};
+
+/// Distance between two layers, in centimeters
+const LAYER_DISTANCE: f64 = 2.5;
+/// Half the distance between two states of the same layer, in centimeters
+const HALF_STATE_DISTANCE: f64 = 0.75;
+
+impl<'a, T, V> InnerAutomata<'a, T, V>
+where
+    T: Eq + Hash + Clone + Display,
+    V: Display,
+{
+    /// Returns the TikZ representation of the automaton, a ```tikzpicture```
+    /// using the ```automata``` library, where the states are placed from left
+    /// to right by [InnerAutomata::layout]. If "doors" is ```true```, the
+    /// states are colored according to their [DoorType] in their strongly
+    /// connected component, as in the DOT representation
+    pub fn to_tikz(&self, doors: bool) -> Result<String, std::fmt::Error> {
+        let layout = self.layout();
+        let mut types: Vec<Option<DoorType>> = vec![None; self.states.len()];
+        if doors {
+            self.get_door().into_iter().flatten().for_each(|(rs, t)| {
+                if let Some(i) = self.states.get_index_of(&rs) {
+                    types[i] = Some(t);
+                }
+            });
+        }
+        let colors = DoorColors::default();
+        let mut f = String::new();
+        writeln!(
+            f,
+            "\\begin{{tikzpicture}}[->, >=latex, auto, semithick, initial text={{}}]"
+        )?;
+        self.states.iter().enumerate().try_for_each(|(i, rs)| {
+            let mut attr = vec!["state"];
+            if self.inputs.contains(rs) {
+                attr.push("initial");
+            }
+            if self.outputs.contains(rs) {
+                attr.push("accepting");
+            }
+            let color = match &types[i] {
+                Some(DoorType::In) => format!("draw={}", colors.input),
+                Some(DoorType::Out) => format!("draw={}", colors.output),
+                Some(DoorType::Both) => format!("draw={}", colors.both),
+                _ => String::new(),
+            };
+            if !color.is_empty() {
+                attr.push(&color);
+            }
+            let (layer, rank) = layout.positions[i];
+            let size = layout.layers[layer].len() as isize;
+            writeln!(
+                f,
+                "\t\\node[{}] (q{}) at ({}, {}) {{${}$}};",
+                attr.join(", "),
+                i,
+                layer as f64 * LAYER_DISTANCE,
+                (size - 1 - 2 * rank as isize) as f64 * HALF_STATE_DISTANCE,
+                escape(&rs.as_ref().get_value().to_string())
+            )
+        })?;
+        self.edges(true)
+            .into_iter()
+            .try_for_each(|(from, symbols, to)| {
+                let (from, to) = (
+                    self.states.get_index_of(from).unwrap_or_default(),
+                    self.states.get_index_of(to).unwrap_or_default(),
+                );
+                let shape = match (from == to, layout.is_back_edge(from, to)) {
+                    (true, _) => " [loop above]",
+                    (false, true) => " [bend left]",
+                    (false, false) => "",
+                };
+                let label: Vec<String> = symbols.iter().map(|s| escape(&s.to_string())).collect();
+                writeln!(
+                    f,
+                    "\t\\path (q{}) edge{} node {{${}$}} (q{});",
+                    from,
+                    shape,
+                    label.join(", "),
+                    to
+                )
+            })?;
+        writeln!(f, "\\end{{tikzpicture}}")?;
+        Ok(f)
+    }
+}
+
+/// Returns "s" where the characters that are special in LaTeX math mode are
+/// escaped
+fn escape(s: &str) -> String {
+    s.chars()
+        .fold(String::with_capacity(s.len()), |mut acc, c| {
+            match c {
+                '\\' => acc.push_str("\\backslash{}"),
+                '~' => acc.push_str("\\sim{}"),
+                '^' => acc.push_str("\\hat{}"),
+                '#' | '$' | '%' | '&' | '_' | '{' | '}' => {
+                    acc.push('\\');
+                    acc.push(c);
+                }
+                c => acc.push(c),
+            }
+            acc
+        })
+}
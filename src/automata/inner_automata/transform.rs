@@ -3,7 +3,11 @@ use super::{
     utils::{Couple, Epsilon, Union},
     InnerAutomata,
 };
-use std::{collections::HashSet, hash::Hash};
+use indexmap::IndexSet;
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
 
 impl<'a, T, V> InnerAutomata<'a, T, V>
 where
@@ -13,9 +17,9 @@ where
     /// Creates a homogeneous automaton that recognizes the same language as the
     /// current automaton
     pub fn homogenize(&self) -> InnerAutomata<'a, T, Couple<Union<T, Epsilon>, V>> {
-        let mut states = HashSet::default();
-        let mut inputs = HashSet::default();
-        let mut outputs = HashSet::default();
+        let mut states = IndexSet::default();
+        let mut inputs = IndexSet::default();
+        let mut outputs = IndexSet::default();
 
         self.states().for_each(|rs| {
             let mut empty = true;
@@ -82,4 +86,167 @@ where
         });
         res
     }
+
+    /// Returns the alphabet of the automaton, in order of appearance, with
+    /// the transitions of the deterministic automaton obtained by the subset
+    /// construction and whether each of its states is final. The state 0 is
+    /// the initial state, and a missing transition is ```None```
+    fn subsets(&self) -> (Vec<T>, Vec<Vec<Option<usize>>>, Vec<bool>) {
+        let states: Vec<&RefState<'a, T, V>> = self.states().collect();
+        let index: HashMap<&RefState<'a, T, V>, usize> =
+            states.iter().enumerate().map(|(i, &rs)| (rs, i)).collect();
+        let mut alphabet = Vec::new();
+        states.iter().for_each(|rs| {
+            rs.as_ref().get_follows().for_each(|(symbol, _)| {
+                if !alphabet.contains(symbol) {
+                    alphabet.push(symbol.clone());
+                }
+            })
+        });
+        let mut start: Vec<usize> = self
+            .inputs()
+            .filter_map(|rs| index.get(rs).copied())
+            .collect();
+        start.sort_unstable();
+        let mut ids: HashMap<Vec<usize>, usize> = HashMap::from([(start.clone(), 0)]);
+        let mut sets = vec![start];
+        let mut delta = Vec::new();
+        let mut queue = VecDeque::from([0]);
+        while let Some(id) = queue.pop_front() {
+            let row = alphabet
+                .iter()
+                .map(|symbol| {
+                    let mut to: Vec<usize> = sets[id]
+                        .iter()
+                        .flat_map(|&i| states[i].as_ref().get_follow(symbol).into_iter().flatten())
+                        .filter_map(|rs| index.get(rs).copied())
+                        .collect();
+                    if to.is_empty() {
+                        return None;
+                    }
+                    to.sort_unstable();
+                    to.dedup();
+                    Some(*ids.entry(to.clone()).or_insert_with(|| {
+                        sets.push(to);
+                        queue.push_back(sets.len() - 1);
+                        sets.len() - 1
+                    }))
+                })
+                .collect();
+            delta.push(row);
+        }
+        let finals = sets
+            .iter()
+            .map(|set| set.iter().any(|&i| self.is_output(states[i])))
+            .collect();
+        (alphabet, delta, finals)
+    }
+
+    /// Creates a deterministic automaton that recognizes the same language as
+    /// the current automaton, using the subset construction. States are
+    /// numbered from 0, the initial state
+    pub fn determinize(&self) -> InnerAutomata<'a, T, usize> {
+        let (alphabet, delta, finals) = self.subsets();
+        InnerAutomata::from_table(&alphabet, &delta, &finals)
+    }
+
+    /// Creates the minimal deterministic automaton, without useless states,
+    /// that recognizes the same language as the current automaton. States are
+    /// numbered from 0, the initial state, in breadth-first order
+    pub fn minimize(&self) -> InnerAutomata<'a, T, usize> {
+        let (alphabet, mut delta, finals) = self.subsets();
+        let mut useful: Vec<bool> = finals.clone();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (from, row) in delta.iter().enumerate() {
+                if !useful[from] && row.iter().any(|to| to.is_some_and(|to| useful[to])) {
+                    useful[from] = true;
+                    changed = true;
+                }
+            }
+        }
+        if !useful[0] {
+            return InnerAutomata::from_table(&alphabet, &[vec![None; alphabet.len()]], &[false]);
+        }
+        delta.iter_mut().for_each(|row| {
+            row.iter_mut().for_each(|to| {
+                if to.is_some_and(|to| !useful[to]) {
+                    *to = None;
+                }
+            })
+        });
+        let mut class: Vec<usize> = finals.iter().map(|&f| f as usize).collect();
+        let mut count = 0;
+        loop {
+            let mut ids: HashMap<(usize, Vec<Option<usize>>), usize> = HashMap::new();
+            let next: Vec<usize> = (0..delta.len())
+                .map(|state| {
+                    let signature = delta[state]
+                        .iter()
+                        .map(|to| to.map(|to| class[to]))
+                        .collect();
+                    let len = ids.len();
+                    *ids.entry((class[state], signature)).or_insert(len)
+                })
+                .collect();
+            class = next;
+            if ids.len() == count {
+                break;
+            }
+            count = ids.len();
+        }
+        let mut order: Vec<Option<usize>> = vec![None; count];
+        let mut minimal_delta = Vec::new();
+        let mut minimal_finals = Vec::new();
+        order[class[0]] = Some(0);
+        let mut next_id = 1;
+        let mut queue = VecDeque::from([0]);
+        while let Some(state) = queue.pop_front() {
+            let row = delta[state]
+                .iter()
+                .map(|to| {
+                    to.map(|to| {
+                        *order[class[to]].get_or_insert_with(|| {
+                            queue.push_back(to);
+                            next_id += 1;
+                            next_id - 1
+                        })
+                    })
+                })
+                .collect();
+            minimal_delta.push(row);
+            minimal_finals.push(finals[state]);
+        }
+        InnerAutomata::from_table(&alphabet, &minimal_delta, &minimal_finals)
+    }
+}
+
+impl<'a, T> InnerAutomata<'a, T, usize>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Creates the deterministic automaton whose state "i" has the transitions
+    /// "delta\[i\]" on the symbols of "alphabet", and is final if
+    /// "finals\[i\]" is ```true```. The state 0 is the initial state
+    fn from_table(alphabet: &[T], delta: &[Vec<Option<usize>>], finals: &[bool]) -> Self {
+        let mut res = InnerAutomata::new();
+        let states: Vec<RefState<'a, T, usize>> = (0..delta.len()).map(RefState::new).collect();
+        let refs: Vec<RefState<'a, T, usize>> = states.to_vec();
+        states.into_iter().for_each(|rs| {
+            res.add_state(rs);
+        });
+        res.add_input(refs[0].clone());
+        delta.iter().enumerate().for_each(|(from, row)| {
+            if finals[from] {
+                res.add_output(refs[from].clone());
+            }
+            row.iter().zip(alphabet).for_each(|(to, symbol)| {
+                if let Some(to) = to {
+                    refs[from].add_follow(refs[*to].clone(), symbol.clone());
+                }
+            })
+        });
+        res
+    }
 }
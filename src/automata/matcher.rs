@@ -0,0 +1,96 @@
+//! Module for the incremental recognition of a word by an automaton, symbol
+//! by symbol
+
+use super::{Accept, RefState};
+use indexmap::IndexSet;
+use std::{collections::HashSet, hash::Hash};
+
+/// Structure reading a word symbol by symbol on an automaton, keeping the set
+/// of active states between two symbols
+pub struct Matcher<'m, 'a, T, V, A>
+where
+    T: Eq + Hash + Clone,
+    V: Eq + Clone,
+    A: Accept<'a, T, V> + ?Sized,
+{
+    /// Automaton on which the word is read
+    automata: &'m A,
+    /// Set of active states
+    current: IndexSet<RefState<'a, T, V>>,
+}
+
+impl<'m, 'a, T, V, A> Matcher<'m, 'a, T, V, A>
+where
+    T: Eq + Hash + Clone,
+    V: Eq + Clone,
+    A: Accept<'a, T, V> + ?Sized,
+{
+    /// Creates a matcher on "automata" that has not read any symbol yet
+    pub fn new(automata: &'m A) -> Self {
+        Self {
+            automata,
+            current: automata.inner().inputs().cloned().collect(),
+        }
+    }
+
+    /// Reads "symbol" and returns ```false``` if no state is active anymore
+    pub fn feed(&mut self, symbol: &T) -> bool {
+        self.current = self
+            .current
+            .iter()
+            .filter_map(|rs| rs.as_ref().get_follow(symbol))
+            .flatten()
+            .cloned()
+            .collect();
+        !self.is_dead()
+    }
+
+    /// Reads each symbol of "word" and returns ```false``` if no state is
+    /// active anymore. The reading stops at the first symbol leaving no active
+    /// state
+    pub fn feed_all<'b>(&mut self, mut word: impl Iterator<Item = &'b T>) -> bool
+    where
+        T: 'b,
+    {
+        word.all(|symbol| self.feed(symbol))
+    }
+
+    /// Returns ```true``` if the symbols read form a word recognized by the
+    /// automaton
+    pub fn is_accepting(&self) -> bool {
+        self.current
+            .iter()
+            .any(|rs| self.automata.inner().is_output(rs))
+    }
+
+    /// Returns ```true``` if no state is active, i.e. if no continuation of the
+    /// symbols read can be recognized
+    pub fn is_dead(&self) -> bool {
+        self.current.is_empty()
+    }
+
+    /// Forgets the symbols read, as if the matcher was just created
+    pub fn reset(&mut self) {
+        self.current = self.automata.inner().inputs().cloned().collect();
+    }
+
+    /// Returns the symbols that can be read without leaving the matcher without
+    /// any active state
+    pub fn allowed_next(&self) -> Vec<T> {
+        let mut seen: HashSet<&T> = HashSet::new();
+        self.current
+            .iter()
+            .flat_map(|rs| rs.as_ref().get_follows())
+            .filter(|(symbol, set)| !set.is_empty() && seen.insert(symbol))
+            .map(|(symbol, _)| symbol.clone())
+            .collect()
+    }
+
+    /// Returns the values of the active states
+    pub fn states(&self) -> Vec<V> {
+        self.current
+            .iter()
+            .map(|rs| rs.as_ref().get_value().clone())
+            .collect()
+    }
+}
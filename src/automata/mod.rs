@@ -2,22 +2,36 @@
 //! hand", checks if a word is recognized by this automata. Finally it can also
 //! be converted into dot format
 
+mod bitglushkov;
+mod document;
 pub mod error;
+pub mod formats;
 mod glushkov;
 mod r#impl;
 mod inner_automata;
+mod matcher;
 
+use crate::regexp::RegExp;
+pub use bitglushkov::BitGlushkov;
+pub use document::{Document, Metadata};
 pub use error::{AutomataError, Result};
 pub use inner_automata::{
+    ambiguity::AmbiguityDegree,
+    dense::DenseDfa,
     dfs::DFSInfo,
     door::DoorType,
+    dot::{DoorColors, DotOptions, RankDir},
+    run::{Outcome, Rejection, Run},
+    search::SearchMode,
+    serde::{FORMAT_VERSION, JSON_SCHEMA},
     state::RefState,
     utils::{Couple, Epsilon, Union},
     InnerAutomata,
 };
+pub use matcher::Matcher;
 use r#impl::Inner;
-use std::collections::HashSet;
 use std::fmt::{Debug, Display};
+use std::ops::Range;
 use std::rc::{Rc, Weak};
 use std::{
     cell::{RefCell, UnsafeCell},
@@ -391,32 +405,72 @@ where
 {
     /// Returns ```true``` if the word is recognized by the automaton and
     /// ```false``` otherwise
-    fn accept<'b>(&self, mut word: impl Iterator<Item = &'b T>) -> bool
+    fn accept<'b>(&self, word: impl Iterator<Item = &'b T>) -> bool
     where
         'a: 'b,
         T: 'b,
     {
-        match word.try_fold(
-            self.inner().inputs().cloned().collect(),
-            |start: Vec<RefState<T, V>>, symbol| {
-                let mut temp: Vec<RefState<T, V>> = Vec::new();
-                start.into_iter().for_each(|rs| {
-                    if let Some(it) = rs.as_ref().get_follow(symbol) {
-                        it.for_each(|rs| temp.push(rs.clone()));
-                    }
-                });
-                if temp.is_empty() {
-                    return Err(());
-                }
-                Ok(temp)
+        let mut matcher = self.matcher();
+        matcher.feed_all(word) && matcher.is_accepting()
+    }
+
+    /// Returns a [Matcher] reading a word on the automaton symbol by symbol
+    fn matcher(&self) -> Matcher<'_, 'a, T, V, Self> {
+        Matcher::new(self)
+    }
+
+    /// Returns the sequence of the sets of active states while reading the
+    /// word, with an accepting path if the word is recognized by the automaton
+    /// and the reason of the rejection otherwise
+    fn run<'b>(&self, word: impl Iterator<Item = &'b T>) -> Run<T, V>
+    where
+        'a: 'b,
+        T: 'b,
+    {
+        let Run { steps, outcome } = self.inner().run(word);
+        Run {
+            steps: steps
+                .into_iter()
+                .map(|l| {
+                    l.into_iter()
+                        .map(|rs| rs.as_ref().get_value().clone())
+                        .collect()
+                })
+                .collect(),
+            outcome: match outcome {
+                Outcome::Accepted { path, symbols } => Outcome::Accepted {
+                    path: path
+                        .into_iter()
+                        .map(|rs| rs.as_ref().get_value().clone())
+                        .collect(),
+                    symbols,
+                },
+                Outcome::Rejected(rejection) => Outcome::Rejected(rejection),
             },
-        ) {
-            Err(()) => false,
-            Ok(it) => it.into_iter().any(|rs| self.inner().is_output(&rs)),
         }
     }
 }
 
+/// Trait for finding the occurrences of the language of an automaton inside a
+/// text
+pub trait Search<'a, T, V>: Inner<'a, T, V>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Returns the ranges of the occurrences of the language of the automaton
+    /// in "text", according to "mode", sorted by start then by end. The empty
+    /// word is reported as an empty range when it is recognized
+    fn find_all(&self, text: &[T], mode: SearchMode) -> Vec<Range<usize>> {
+        self.inner().find_all(text, mode)
+    }
+
+    /// Returns the leftmost longest occurrence of the language of the
+    /// automaton in "text", or ```None``` if there is none
+    fn find(&self, text: &[T]) -> Option<Range<usize>> {
+        self.inner().find(text)
+    }
+}
+
 /// Trait allowing the copy of an automaton
 pub trait Cloned<'a, T, V>: Inner<'a, T, V>
 where
@@ -546,6 +600,84 @@ where
     fn to_dot(&self, inverse: bool) -> std::result::Result<String, std::fmt::Error> {
         self.inner().to_dot(inverse)
     }
+
+    /// Returns the DOT representation of the automaton according to "options"
+    fn to_dot_with(
+        &self,
+        options: &DotOptions<T, V>,
+    ) -> std::result::Result<String, std::fmt::Error> {
+        self.inner().to_dot_with(options)
+    }
+}
+
+/// Line for Mermaid representation of the automaton
+pub trait ToMermaid<'a, T, V>: Inner<'a, T, V>
+where
+    T: Eq + Hash + Clone + Display,
+    V: Eq + Clone + Display,
+{
+    /// Returns the Mermaid representation of the automaton, a
+    /// ```stateDiagram-v2```
+    fn to_mermaid(&self) -> std::result::Result<String, std::fmt::Error> {
+        self.inner().to_mermaid()
+    }
+}
+
+/// Line for GraphML representation of the automaton
+pub trait ToGraphML<'a, T, V>: Inner<'a, T, V>
+where
+    T: Eq + Hash + Clone + Display,
+    V: Eq + Clone + Display,
+{
+    /// Returns the GraphML representation of the automaton, where each node
+    /// carries whether it is initial or final, its strongly connected
+    /// component and its [DoorType]
+    fn to_graphml(&self) -> std::result::Result<String, std::fmt::Error> {
+        self.inner().to_graphml()
+    }
+}
+
+/// Line for SVG representation of the automaton
+pub trait ToSvg<'a, T, V>: Inner<'a, T, V>
+where
+    T: Eq + Hash + Clone + Display,
+    V: Eq + Clone + Display,
+{
+    /// Returns the SVG representation of the automaton, drawn without
+    /// Graphviz, with each orbit framed if "orbits" is ```true```
+    fn to_svg(&self, orbits: bool) -> std::result::Result<String, std::fmt::Error> {
+        self.inner().to_svg(orbits)
+    }
+}
+
+/// Line for TikZ representation of the automaton
+pub trait ToTikz<'a, T, V>: Inner<'a, T, V>
+where
+    T: Eq + Hash + Clone + Display,
+    V: Eq + Clone + Display,
+{
+    /// Returns the TikZ representation of the automaton, a ```tikzpicture```
+    /// requiring ```\usetikzlibrary{automata}```, with the states colored
+    /// according to their [DoorType] if "doors" is ```true```
+    fn to_tikz(&self, doors: bool) -> std::result::Result<String, std::fmt::Error> {
+        self.inner().to_tikz(doors)
+    }
+}
+
+/// Trait for generating the Rust source code of a function recognizing the
+/// language of the automaton
+pub trait ToRust<'a, V>: Inner<'a, char, V>
+where
+    V: Eq + Clone,
+{
+    /// Returns the source code of a function named "name", of signature
+    /// ```fn(&str) -> bool```, returning whether its argument is recognized.
+    /// The function is a ```match``` over the states of the minimal
+    /// deterministic automaton and does not depend on this crate. Returns an
+    /// error if "name" is not an identifier or is a keyword
+    fn to_rust(&self, name: &str) -> Result<String> {
+        self.inner().to_rust(name)
+    }
 }
 
 /// Trait to define a method for extracting a sub-automaton from an automaton
@@ -641,6 +773,22 @@ where
     fn is_strongly_transverse(&self) -> bool {
         self.inner().is_strongly_transverse()
     }
+
+    /// Returns whether each accepted word has exactly one accepting run
+    fn is_unambiguous(&self) -> bool {
+        self.inner().is_unambiguous()
+    }
+
+    /// Returns the degree of ambiguity of the automaton
+    fn ambiguity_degree(&self) -> AmbiguityDegree {
+        self.inner().ambiguity_degree()
+    }
+
+    /// Returns a shortest word with at least two accepting runs, or ```None```
+    /// if the automaton is unambiguous
+    fn ambiguous_word(&self) -> Option<Vec<T>> {
+        self.inner().ambiguous_word()
+    }
 }
 
 /// Trait grouping all methods for converting an automaton
@@ -659,4 +807,46 @@ where
             }),
         }
     }
+
+    /// Creates a deterministic automaton that recognizes the same language as
+    /// the current automaton, using the subset construction. States are
+    /// numbered from 0, the initial state
+    fn determinize(&self) -> Automata<'a, T, usize> {
+        Automata {
+            himself: UnsafeCell::new(InnerParent {
+                inner: self.inner().determinize(),
+                childs: Vec::default(),
+            }),
+        }
+    }
+
+    /// Returns a one-unambiguous regular expression recognizing the same
+    /// language as the automaton, built with the algorithm of Brüggemann-Klein
+    /// and Wood. Returns ```None``` if the language is not one-unambiguous,
+    /// i.e. if no deterministic regular expression describes it, or if it is
+    /// empty
+    fn to_one_unambiguous(&self) -> Option<RegExp<T>> {
+        self.inner().to_one_unambiguous()
+    }
+
+    /// Creates the minimal deterministic automaton, without useless states,
+    /// that recognizes the same language as the current automaton. States are
+    /// numbered from 0, the initial state, in breadth-first order
+    fn minimize(&self) -> Automata<'a, T, usize> {
+        Automata {
+            himself: UnsafeCell::new(InnerParent {
+                inner: self.inner().minimize(),
+                childs: Vec::default(),
+            }),
+        }
+    }
+
+    /// Compiles the minimal deterministic automaton recognizing the same
+    /// language as the current automaton into a [DenseDfa]
+    fn compile(&self) -> DenseDfa<T>
+    where
+        T: Ord,
+    {
+        self.inner().compile()
+    }
 }
@@ -0,0 +1,491 @@
+//! Command-line tool for building, analysing and converting automata. Files
+//! are read in the serialization format of the library, in JSON or CBOR, or in
+//! the formats of [glushkovizer::automata::formats], and a missing file or
+//! ```-``` is the standard input
+
+use glushkovizer::automata::{formats, Automata, DenseDfa};
+use glushkovizer::prelude::*;
+use glushkovizer::regexp::RegExp;
+use serde::Serialize;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    fs,
+    hash::Hash,
+    io::{self, stdin, stdout, ErrorKind, Read, Write},
+    path::Path,
+    process::ExitCode,
+};
+
+const USAGE: &str = "Usage: glushkovizer <command> [options] [arguments]
+
+Commands:
+  glushkov [regex]          Glushkov automaton of a regular expression
+  props [file]              Properties of an automaton
+  orbits [file]             Orbits of an automaton, one per line
+  accept <file> <word>...   Whether an automaton recognizes each word
+  determinize [file]        Deterministic automaton, by the subset construction
+  minimize [file]           Minimal deterministic automaton
+  equiv <file> <file>       Whether two automata recognize the same language
+
+Options:
+  --format <format>   Format of the automata written: dot (default), json,
+                      cbor or svg
+  --from <format>     Format of the files read: json, cbor, dot, jflap, att,
+                      hoa or regex, guessed from the extension or the content
+                      by default. AT&T files are only recognized by their
+                      extension, .att or .fst
+  -h, --help          Print this help
+  -V, --version       Print the version
+
+A missing file or \"-\" is read from the standard input, and so is a missing
+regular expression. The symbols of a word are its characters.
+
+Exit status: 0 on success or a positive answer, 1 if a word is rejected or the
+automata are not equivalent, 2 on error.";
+
+/// Exit status of a negative answer
+const NEGATIVE: u8 = 1;
+/// Exit status of an error
+const ERROR: u8 = 2;
+
+/// Automaton read from a file, whose symbols and states are named
+type Named<'a> = Automata<'a, String, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Format of the automata written
+enum Output {
+    Dot,
+    Json,
+    Cbor,
+    Svg,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Format of the files read
+enum Input {
+    Json,
+    Cbor,
+    Dot,
+    Jflap,
+    Att,
+    Hoa,
+    Regex,
+}
+
+/// Error of a command
+enum Failure {
+    /// Error to report
+    Message(String),
+    /// Error of the standard output
+    Output(io::Error),
+}
+
+impl From<String> for Failure {
+    fn from(message: String) -> Self {
+        Failure::Message(message)
+    }
+}
+
+impl From<io::Error> for Failure {
+    fn from(e: io::Error) -> Self {
+        Failure::Output(e)
+    }
+}
+
+/// Options and arguments of a command
+struct Options {
+    output: Output,
+    input: Option<Input>,
+    arguments: Vec<String>,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut out = stdout().lock();
+    match run(&args, &mut out).and_then(|code| Ok(out.flush().map(|_| code)?)) {
+        Ok(code) => ExitCode::from(code),
+        // The reader of the output stopped reading, as "head" does
+        Err(Failure::Output(e)) if e.kind() == ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(Failure::Output(e)) => {
+            eprintln!("glushkovizer: cannot write the output: {}", e);
+            ExitCode::from(ERROR)
+        }
+        Err(Failure::Message(e)) => {
+            eprintln!("glushkovizer: {}", e);
+            ExitCode::from(ERROR)
+        }
+    }
+}
+
+/// Runs the command of "args" writing in "out", returning its exit status
+fn run(args: &[String], out: &mut impl Write) -> Result<u8, Failure> {
+    let Some(command) = args.first() else {
+        return Err(format!("missing command\n\n{}", USAGE).into());
+    };
+    match command.as_str() {
+        "-h" | "--help" => {
+            writeln!(out, "{}", USAGE)?;
+            return Ok(0);
+        }
+        "-V" | "--version" => {
+            writeln!(out, "glushkovizer {}", env!("CARGO_PKG_VERSION"))?;
+            return Ok(0);
+        }
+        _ => {}
+    }
+    let options = parse_options(&args[1..])?;
+    let arguments = &options.arguments;
+    let count = |min: usize, max: usize| match arguments.len() {
+        n if n < min => Err(format!("'{}' expects more arguments\n\n{}", command, USAGE)),
+        n if n > max => Err(format!("'{}' expects less arguments\n\n{}", command, USAGE)),
+        _ => Ok(()),
+    };
+    let file = |i: usize| arguments.get(i).map(String::as_str);
+    match command.as_str() {
+        "glushkov" => {
+            count(0, 1)?;
+            let regex = match file(0) {
+                Some(regex) => regex.to_string(),
+                None => String::from_utf8(read(None)?)
+                    .map_err(|_| String::from("the regular expression is not in UTF-8"))?,
+            };
+            let regex = RegExp::try_from(regex.trim())?;
+            write(out, &Automata::from(regex), options.output)?;
+        }
+        "props" => {
+            count(0, 1)?;
+            props(out, &load(file(0), options.input)?)?;
+        }
+        "orbits" => {
+            count(0, 1)?;
+            let a = load(file(0), options.input)?;
+            for orbit in a.extract_scc().into_iter().filter(|sub| sub.is_orbit()) {
+                writeln!(out, "{}", orbit.states().join(" "))?;
+            }
+        }
+        "accept" => {
+            count(2, usize::MAX)?;
+            let a = load(file(0), options.input)?;
+            let mut code = 0;
+            for word in &arguments[1..] {
+                let symbols: Vec<String> = word.chars().map(String::from).collect();
+                let accepted = a.accept(symbols.iter());
+                writeln!(
+                    out,
+                    "{}\t{}",
+                    word,
+                    if accepted { "accepted" } else { "rejected" }
+                )?;
+                if !accepted {
+                    code = NEGATIVE;
+                }
+            }
+            return Ok(code);
+        }
+        "determinize" => {
+            count(0, 1)?;
+            write(
+                out,
+                &load(file(0), options.input)?.determinize(),
+                options.output,
+            )?;
+        }
+        "minimize" => {
+            count(0, 1)?;
+            write(
+                out,
+                &load(file(0), options.input)?.minimize(),
+                options.output,
+            )?;
+        }
+        "equiv" => {
+            count(2, 2)?;
+            if file(0) == Some("-") && file(1) == Some("-") {
+                return Err(String::from("only one automaton can be read from stdin").into());
+            }
+            let a = load(file(0), options.input)?.compile();
+            let b = load(file(1), options.input)?.compile();
+            match distinguish(&a, &b) {
+                None => writeln!(out, "equivalent")?,
+                Some((word, first)) => {
+                    writeln!(
+                        out,
+                        "not equivalent: \"{}\" is only recognized by the {} automaton",
+                        word.concat(),
+                        if first { "first" } else { "second" }
+                    )?;
+                    return Ok(NEGATIVE);
+                }
+            }
+        }
+        _ => return Err(format!("unknown command '{}'\n\n{}", command, USAGE).into()),
+    }
+    Ok(0)
+}
+
+/// Returns the options and the arguments of "args"
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        output: Output::Dot,
+        input: None,
+        arguments: Vec::new(),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .map(String::as_str)
+                .ok_or_else(|| format!("missing value of '{}'", name))
+        };
+        match arg.as_str() {
+            "--format" => {
+                options.output = match value("--format")? {
+                    "dot" => Output::Dot,
+                    "json" => Output::Json,
+                    "cbor" => Output::Cbor,
+                    "svg" => Output::Svg,
+                    f => return Err(format!("unknown output format '{}'", f)),
+                }
+            }
+            "--from" => {
+                options.input = Some(match value("--from")? {
+                    "json" => Input::Json,
+                    "cbor" => Input::Cbor,
+                    "dot" => Input::Dot,
+                    "jflap" => Input::Jflap,
+                    "att" => Input::Att,
+                    "hoa" => Input::Hoa,
+                    "regex" => Input::Regex,
+                    f => return Err(format!("unknown input format '{}'", f)),
+                })
+            }
+            "--" => options.arguments.extend(args.by_ref().cloned()),
+            a if a.starts_with("--") => return Err(format!("unknown option '{}'", a)),
+            _ => options.arguments.push(arg.clone()),
+        }
+    }
+    Ok(options)
+}
+
+/// Returns the content of the file "path", or of the standard input if it is
+/// missing or ```-```
+fn read(path: Option<&str>) -> Result<Vec<u8>, String> {
+    match path {
+        None | Some("-") => {
+            let mut content = Vec::new();
+            stdin()
+                .read_to_end(&mut content)
+                .map_err(|e| format!("cannot read the standard input: {}", e))?;
+            Ok(content)
+        }
+        Some(path) => fs::read(path).map_err(|e| format!("cannot read '{}': {}", path, e)),
+    }
+}
+
+/// Returns the format of the file "path" of content "content", from its
+/// extension or else from its content
+fn guess(path: Option<&str>, content: &[u8]) -> Input {
+    let extension = path
+        .and_then(|p| Path::new(p).extension())
+        .and_then(|e| e.to_str());
+    match extension {
+        Some("json") => return Input::Json,
+        Some("cbor") => return Input::Cbor,
+        Some("dot" | "gv") => return Input::Dot,
+        Some("jff") => return Input::Jflap,
+        Some("att" | "fst") => return Input::Att,
+        Some("hoa") => return Input::Hoa,
+        _ => {}
+    }
+    let Ok(text) = std::str::from_utf8(content) else {
+        return Input::Cbor;
+    };
+    let text = text.trim_start();
+    if text.starts_with('{') {
+        Input::Json
+    } else if text.starts_with("HOA:") {
+        Input::Hoa
+    } else if text.starts_with('<') {
+        Input::Jflap
+    } else if ["digraph", "graph", "strict"]
+        .iter()
+        .any(|k| text.starts_with(k))
+    {
+        Input::Dot
+    } else {
+        Input::Regex
+    }
+}
+
+/// Returns the automaton of the file "path", or of the standard input if it is
+/// missing or ```-```, in the format "input" or else a guessed format
+fn load<'a>(path: Option<&str>, input: Option<Input>) -> Result<Named<'a>, String> {
+    let content = read(path)?;
+    let input = input.unwrap_or_else(|| guess(path, &content));
+    let text = || {
+        std::str::from_utf8(&content)
+            .map_err(|_| format!("{} is not in UTF-8", path.unwrap_or("the standard input")))
+    };
+    match input {
+        Input::Json => {
+            let a: Automata<String, serde_json::Value> =
+                serde_json::from_slice(&content).map_err(|e| e.to_string())?;
+            named(&a, value)
+        }
+        Input::Cbor => {
+            let a: Automata<String, serde_json::Value> =
+                serde_cbor::from_slice(&content).map_err(|e| e.to_string())?;
+            named(&a, value)
+        }
+        Input::Dot => formats::from_dot(text()?).map_err(|e| e.to_string()),
+        Input::Jflap => {
+            let (a, _) = formats::from_jflap(text()?).map_err(|e| e.to_string())?;
+            named(&a, usize::to_string)
+        }
+        Input::Att => named(
+            &formats::from_att(text()?).map_err(|e| e.to_string())?,
+            usize::to_string,
+        ),
+        Input::Hoa => named(
+            &formats::from_hoa(text()?).map_err(|e| e.to_string())?,
+            usize::to_string,
+        ),
+        Input::Regex => {
+            let regex = RegExp::try_from(text()?.trim())?;
+            named(&Automata::from(regex), usize::to_string)
+        }
+    }
+}
+
+/// Returns the name of a state read from JSON or CBOR, which is the string
+/// itself for a string
+fn value(v: &serde_json::Value) -> String {
+    match v {
+        serde_json::Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+/// Returns the copy of "a" whose symbols are named by their display and whose
+/// states are named by "name", which must name them differently
+fn named<'a, 'b, T, V>(
+    a: &Automata<'b, T, V>,
+    name: impl Fn(&V) -> String,
+) -> Result<Named<'a>, String>
+where
+    T: Eq + Hash + Clone + Display,
+    V: Eq + Clone,
+{
+    let res = Automata::new();
+    for state in a.states() {
+        if !res.add_state(name(&state)) {
+            return Err(format!("two states are named '{}'", name(&state)));
+        }
+    }
+    let error = |e: glushkovizer::automata::AutomataError| e.to_string();
+    for state in a.inputs() {
+        res.add_input(&name(&state)).map_err(error)?;
+    }
+    for state in a.outputs() {
+        res.add_output(&name(&state)).map_err(error)?;
+    }
+    for from in a.states() {
+        for (symbol, tos) in a.get_follows(&from).map_err(error)? {
+            for to in tos {
+                res.add_transition(&name(&from), &name(&to), symbol.to_string())
+                    .map_err(error)?;
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// Writes in "out" the results of [Properties] on "a"
+fn props(out: &mut impl Write, a: &Named) -> io::Result<()> {
+    let properties = [
+        ("standard", a.is_standard()),
+        ("deterministic", a.is_deterministic()),
+        ("fully deterministic", a.is_fully_deterministic()),
+        ("homogeneous", a.is_homogeneous()),
+        ("accessible", a.is_accessible()),
+        ("coaccessible", a.is_coaccessible()),
+        ("strongly connected", a.is_strongly_connected()),
+        ("orbit", a.is_orbit()),
+        ("stable", a.is_stable()),
+        ("strongly stable", a.is_strongly_stable()),
+        ("transverse", a.is_transverse()),
+        ("strongly transverse", a.is_strongly_transverse()),
+        ("unambiguous", a.is_unambiguous()),
+    ];
+    for (name, value) in properties {
+        writeln!(out, "{}: {}", name, value)?;
+    }
+    writeln!(out, "ambiguity degree: {:?}", a.ambiguity_degree())?;
+    match a.ambiguous_word() {
+        None => writeln!(out, "ambiguous word: none"),
+        Some(word) => writeln!(out, "ambiguous word: \"{}\"", word.concat()),
+    }
+}
+
+/// Writes "a" in "out" in the format "output"
+fn write<'a, T, V>(
+    out: &mut impl Write,
+    a: &Automata<'a, T, V>,
+    output: Output,
+) -> Result<(), Failure>
+where
+    T: Eq + Hash + Clone + Display + Serialize,
+    V: Eq + Clone + Display + Serialize,
+{
+    let bytes = match output {
+        Output::Dot => a.to_dot(false).map_err(|e| e.to_string())?.into_bytes(),
+        Output::Svg => a.to_svg(true).map_err(|e| e.to_string())?.into_bytes(),
+        Output::Json => {
+            let mut json = serde_json::to_vec(a).map_err(|e| e.to_string())?;
+            json.push(b'\n');
+            json
+        }
+        Output::Cbor => serde_cbor::to_vec(a).map_err(|e| e.to_string())?,
+    };
+    Ok(out.write_all(&bytes)?)
+}
+
+/// Returns a shortest word recognized by only one of "a" and "b", and whether
+/// it is recognized by "a", or ```None``` if they recognize the same language
+fn distinguish<T>(a: &DenseDfa<T>, b: &DenseDfa<T>) -> Option<(Vec<T>, bool)>
+where
+    T: Ord + Clone,
+{
+    let mut symbols: Vec<&T> = a.symbols().iter().chain(b.symbols()).collect();
+    symbols.sort();
+    symbols.dedup();
+    let is_final = |d: &DenseDfa<T>, s: Option<u32>| s.is_some_and(|s| d.is_final(s));
+    let start = (Some(0), Some(0));
+    let mut previous: HashMap<(Option<u32>, Option<u32>), Option<(_, &T)>> =
+        HashMap::from([(start, None)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(pair @ (sa, sb)) = queue.pop_front() {
+        if is_final(a, sa) != is_final(b, sb) {
+            let mut word = Vec::new();
+            let mut current = pair;
+            while let Some(Some((before, symbol))) = previous.get(&current) {
+                word.push((*symbol).clone());
+                current = *before;
+            }
+            word.reverse();
+            return Some((word, is_final(a, sa)));
+        }
+        for symbol in &symbols {
+            let next = (
+                sa.and_then(|s| a.next_state(s, symbol)),
+                sb.and_then(|s| b.next_state(s, symbol)),
+            );
+            if next != (None, None) && !previous.contains_key(&next) {
+                previous.insert(next, Some((pair, *symbol)));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
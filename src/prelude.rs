@@ -2,6 +2,7 @@
 
 pub use crate::automata::{
     Accept, AddStates, Cloned, Couple, Epsilon, ExtractStronglyConnectedComponent,
-    ExtractSubAutomata, InOut, Kosaraju, Mirror, MutTransition, Properties, RemoveStates,
-    StatesInfo, ToDot, Transform, TransitionInfo, Union, DFS,
+    ExtractSubAutomata, InOut, Kosaraju, Mirror, MutTransition, Properties, RemoveStates, Search,
+    StatesInfo, ToDot, ToGraphML, ToMermaid, ToRust, ToSvg, ToTikz, Transform, TransitionInfo,
+    Union, DFS,
 };
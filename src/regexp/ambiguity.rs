@@ -0,0 +1,275 @@
+//! Module for testing the unambiguity of regular expressions
+
+use super::{Numbered, RegExp};
+use crate::automata::{Automata, Properties, Transform};
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Structure describing why a regular expression is not one-unambiguous: after
+/// reading "prefix", the next symbol can be matched by two positions
+pub struct Conflict<T> {
+    /// Position with the smallest number among the two competing positions
+    pub first: Numbered<T>,
+    /// Position with the largest number among the two competing positions
+    pub second: Numbered<T>,
+    /// Shortest word after which "first" and "second" compete
+    pub prefix: Vec<T>,
+}
+
+impl<T> RegExp<T>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Returns ```Ok(())``` if the regular expression is one-unambiguous (or
+    /// deterministic), i.e. if its Glushkov automaton is deterministic, and
+    /// otherwise the [Conflict] reached with the shortest prefix
+    pub fn is_one_unambiguous(&self) -> Result<(), Conflict<T>> {
+        let info = self.linearization().get_flnf();
+        let mut predecessor: HashMap<usize, Option<&Numbered<T>>> = HashMap::new();
+        let mut queue: VecDeque<Option<&Numbered<T>>> = VecDeque::from([None]);
+        while let Some(from) = queue.pop_front() {
+            let mut next: Vec<&Numbered<T>> = match from {
+                None => info.firsts.iter().collect(),
+                Some(p) => info
+                    .follows
+                    .get(p)
+                    .map(|s| s.iter().collect())
+                    .unwrap_or_default(),
+            };
+            next.sort_by_key(|p| p.1);
+            let mut symbols: HashMap<&T, &Numbered<T>> = HashMap::with_capacity(next.len());
+            for p in next.iter() {
+                if let Some(&q) = symbols.get(&p.0) {
+                    return Err(Conflict {
+                        first: q.clone(),
+                        second: (*p).clone(),
+                        prefix: prefix(&predecessor, from),
+                    });
+                }
+                symbols.insert(&p.0, p);
+            }
+            for p in next {
+                if let Entry::Vacant(e) = predecessor.entry(p.1) {
+                    e.insert(from);
+                    queue.push_back(Some(p));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> RegExp<T>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Returns a one-unambiguous regular expression recognizing the same
+    /// language as the regular expression, built with the algorithm of
+    /// Brüggemann-Klein and Wood, or ```None``` if there is none
+    pub fn to_one_unambiguous(&self) -> Option<RegExp<T>> {
+        Automata::from(self.clone()).to_one_unambiguous()
+    }
+}
+
+impl<T> RegExp<T>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Returns ```Ok(())``` if the regular expression is strongly unambiguous,
+    /// i.e. if each word of its language has a single parse, and otherwise a
+    /// word with two parses.
+    ///
+    /// According to Brüggemann-Klein, this is the case if and only if the
+    /// expression is in epsilon normal form, in star normal form and if its
+    /// Glushkov automaton is unambiguous
+    pub fn is_strongly_unambiguous(&self) -> Result<(), Vec<T>> {
+        self.normal_forms(&[], &[])?;
+        match Automata::from(self.clone()).ambiguous_word() {
+            Some(word) => Err(word),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that the regular expression is in epsilon normal form and in
+    /// star normal form, where "pre" and "post" are words surrounding it in
+    /// the whole expression
+    fn normal_forms(&self, pre: &[T], post: &[T]) -> Result<(), Vec<T>> {
+        match self {
+            RegExp::Epsilon | RegExp::Symbol(_) => Ok(()),
+            RegExp::Or(left, right) => {
+                if left.nullable() && right.nullable() {
+                    return Err([pre, post].concat());
+                }
+                left.normal_forms(pre, post)?;
+                right.normal_forms(pre, post)
+            }
+            RegExp::Concat(left, right) => {
+                left.normal_forms(pre, &[right.shortest_word().as_slice(), post].concat())?;
+                right.normal_forms(&[pre, left.shortest_word().as_slice()].concat(), post)
+            }
+            RegExp::Repeat(inner) => {
+                if inner.nullable() {
+                    return Err([pre, post].concat());
+                }
+                let info = inner.linearization().get_flnf();
+                let empty = HashSet::new();
+                for last in info.lasts.iter() {
+                    let follows = info.follows.get(last).unwrap_or(&empty);
+                    if let Some(first) = follows.intersection(&info.firsts).next() {
+                        let to = shortest_path(info.firsts.iter(), |p| p == last, &info.follows);
+                        let from =
+                            shortest_path([first], |p| info.lasts.contains(p), &info.follows);
+                        if let (Some(to), Some(from)) = (to, from) {
+                            return Err([pre, &to, &from, post].concat());
+                        }
+                    }
+                }
+                inner.normal_forms(pre, post)
+            }
+        }
+    }
+
+    /// Returns one of the shortest words of the language of the regular
+    /// expression
+    fn shortest_word(&self) -> Vec<T> {
+        match self {
+            RegExp::Epsilon | RegExp::Repeat(_) => Vec::new(),
+            RegExp::Symbol(v) => vec![v.clone()],
+            RegExp::Concat(left, right) => [left.shortest_word(), right.shortest_word()].concat(),
+            RegExp::Or(left, right) => {
+                let (left, right) = (left.shortest_word(), right.shortest_word());
+                if right.len() < left.len() {
+                    right
+                } else {
+                    left
+                }
+            }
+        }
+    }
+}
+
+/// Returns the symbols of a shortest path starting from one of "starts" and
+/// ending on a position satisfying "goal", according to "follows"
+fn shortest_path<'a, T, I, F>(
+    starts: I,
+    goal: F,
+    follows: &'a HashMap<Numbered<T>, HashSet<Numbered<T>>>,
+) -> Option<Vec<T>>
+where
+    T: Eq + Hash + Clone + 'a,
+    I: IntoIterator<Item = &'a Numbered<T>>,
+    F: Fn(&Numbered<T>) -> bool,
+{
+    let mut predecessor: HashMap<usize, Option<&Numbered<T>>> = HashMap::new();
+    let mut queue: VecDeque<&Numbered<T>> = VecDeque::new();
+    for p in starts {
+        if let Entry::Vacant(e) = predecessor.entry(p.1) {
+            e.insert(None);
+            queue.push_back(p);
+        }
+    }
+    while let Some(p) = queue.pop_front() {
+        if goal(p) {
+            return Some(prefix(&predecessor, Some(p)));
+        }
+        for q in follows.get(p).into_iter().flatten() {
+            if let Entry::Vacant(e) = predecessor.entry(q.1) {
+                e.insert(Some(p));
+                queue.push_back(q);
+            }
+        }
+    }
+    None
+}
+
+/// Returns the word leading to "to" according to the "predecessor" relation
+fn prefix<'a, T: Clone>(
+    predecessor: &HashMap<usize, Option<&'a Numbered<T>>>,
+    mut to: Option<&'a Numbered<T>>,
+) -> Vec<T> {
+    let mut word = Vec::new();
+    while let Some(p) = to {
+        word.push(p.0.clone());
+        to = predecessor.get(&p.1).copied().flatten();
+    }
+    word.reverse();
+    word
+}
+
+#[cfg(test)]
+mod test {
+    use super::Conflict;
+    use crate::regexp::{Numbered, RegExp};
+
+    #[test]
+    fn deterministic() {
+        let a = RegExp::try_from("(a+b)*.c").unwrap();
+        assert_eq!(Ok(()), a.is_one_unambiguous());
+        let a = RegExp::try_from("b.(a.b+$).a").unwrap();
+        assert!(a.is_one_unambiguous().is_err());
+    }
+
+    #[test]
+    fn bkw() {
+        let a = RegExp::try_from("a.b+a.c").unwrap();
+        let b = a.to_one_unambiguous().unwrap();
+        assert_eq!(Ok(()), b.is_one_unambiguous());
+        let a = RegExp::try_from("(a+b)*.a").unwrap();
+        let b = a.to_one_unambiguous().unwrap();
+        assert_eq!(Ok(()), b.is_one_unambiguous());
+        let a = RegExp::try_from("(a+b)*.a.(a+b)").unwrap();
+        assert_eq!(None, a.to_one_unambiguous());
+        let a = RegExp::try_from("(a.b+b)*.(a+$)").unwrap();
+        let b = a.to_one_unambiguous().unwrap();
+        assert_eq!(Ok(()), b.is_one_unambiguous());
+        let a = RegExp::try_from("b*.a.(b*.a)*").unwrap();
+        assert!(a.to_one_unambiguous().is_some());
+        let a = RegExp::try_from("$").unwrap();
+        assert_eq!(Some(RegExp::Epsilon), a.to_one_unambiguous());
+    }
+
+    #[test]
+    fn conflict() {
+        let a = RegExp::try_from("(a+b)*.a").unwrap();
+        assert_eq!(
+            Err(Conflict {
+                first: Numbered('a', 1),
+                second: Numbered('a', 3),
+                prefix: vec![],
+            }),
+            a.is_one_unambiguous()
+        );
+        let a = RegExp::try_from("c.d.(a.b+a.c)").unwrap();
+        assert_eq!(
+            Err(Conflict {
+                first: Numbered('a', 3),
+                second: Numbered('a', 5),
+                prefix: vec!['c', 'd'],
+            }),
+            a.is_one_unambiguous()
+        );
+    }
+
+    #[test]
+    fn strongly_unambiguous() {
+        let a = RegExp::try_from("(a.b*)*.c").unwrap();
+        assert_eq!(Ok(()), a.is_strongly_unambiguous());
+        let a = RegExp::try_from("(a+$)*").unwrap();
+        assert_eq!(Err(vec![]), a.is_strongly_unambiguous());
+        let a = RegExp::try_from("c.(a+$+b*)").unwrap();
+        assert_eq!(Err(vec!['c']), a.is_strongly_unambiguous());
+        let a = RegExp::try_from("(a*.b)*").unwrap();
+        assert_eq!(Ok(()), a.is_strongly_unambiguous());
+        let a = RegExp::try_from("(a.b*)*").unwrap();
+        assert_eq!(Ok(()), a.is_strongly_unambiguous());
+        let a = RegExp::try_from("(a*+b)*").unwrap();
+        assert_eq!(Err(vec![]), a.is_strongly_unambiguous());
+        let a = RegExp::try_from("(a+a.b+b)*").unwrap();
+        assert_eq!(Err(vec!['a', 'b']), a.is_strongly_unambiguous());
+        let a = RegExp::try_from("c.(a+a)").unwrap();
+        assert_eq!(Err(vec!['c', 'a']), a.is_strongly_unambiguous());
+    }
+}
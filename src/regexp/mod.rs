@@ -2,8 +2,12 @@
 //! expression "by hand" or "parse" it from a string containing the following
 //! operations:
 //!
-//! - ```a```: Where "a" is a letter of the alphabet between 'a' and 'z' or 'A'
-//!     and 'Z'
+//! - ```a```: Where "a" is any Unicode character other than whitespace and
+//!     the reserved characters ```+*().$'<>```
+//!
+//! - ```'name'``` or ```<name>```: A symbol made of several characters, for
+//!     example an XML element name. Its parsing is described below the
+//!     list
 //!
 //! - ```$```: Character used to represent epsilon (the empty word)
 //!
@@ -16,6 +20,14 @@
 //! - ```expr+expr```: Represents the "or" between the two regular
 //!     expressions
 //!
+//! Symbols are ```char``` when using [TryFrom], but any type can be used as
+//! symbol with [RegExp::parse] and [RegExp::parse_with].
+//! A name needs a symbol type able to hold it, such as ```String``` with
+//! [RegExp::parse] or [RegExp::parse_with]: ```RegExp<char>``` rejects it
+//! as having too many characters, unless the name is a single character.
+//! Between quotes, ```\``` escapes the next character, so ```'it\'s'``` is
+//! the symbol "it's".
+//!
 //! # Example
 //!
 //! ```rust
@@ -31,19 +43,30 @@
 //! }
 //! ```
 
+mod ambiguity;
+mod pretty;
+mod simplify;
+mod stats;
+
+pub use ambiguity::Conflict;
 use lrlex::lrlex_mod;
-use lrpar::lrpar_mod;
+use lrpar::{lrpar_mod, Span};
+pub use pretty::{Pretty, PrettyOptions, Spacing};
 use serde::{Deserialize, Serialize};
+pub use simplify::Rewrite;
+pub use stats::RegExpStats;
 use std::{
+    borrow::Cow,
     collections::{HashMap, HashSet},
     fmt::{self, Debug, Display, Formatter},
     hash::Hash,
+    str::FromStr,
 };
 
 lrlex_mod!("regexp/reg.l");
 lrpar_mod!("regexp/reg.y");
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 /// Name of an enum whose purpose is to represent a regular expression using a
 /// tree, composed of T-type symbols
 pub enum RegExp<T> {
@@ -62,24 +85,126 @@ pub enum RegExp<T> {
     Or(Box<RegExp<T>>, Box<RegExp<T>>),
 }
 
+/// Parses "regexp" and returns the expression tree where each symbol is the
+/// span of its token in "regexp"
+fn parse_spans(regexp: &str) -> Result<RegExp<Span>, String> {
+    let lexerdef = reg_l::lexerdef();
+    let lexer = lexerdef.lexer(regexp);
+    let (res, errs) = reg_y::parse(&lexer);
+    let mut err = String::new();
+    for e in &errs {
+        err.push_str(&format!("{}\n", e.pp(&lexer, &reg_y::token_epp)));
+    }
+    match res {
+        Some(Ok(r)) if errs.is_empty() => Ok(r),
+        _ => {
+            err.push_str("Unable to evaluate expression.");
+            Err(err)
+        }
+    }
+}
+
+/// Returns the text of the symbol at "span" in "regexp", without the quotes
+/// or angle brackets surrounding multi-character symbols, nor the escapes
+/// between quotes
+fn symbol_str(regexp: &str, span: Span) -> Cow<'_, str> {
+    let s = &regexp[span.start()..span.end()];
+    match s.chars().next() {
+        Some('\'') if s.len() > 1 => unescape(&s[1..s.len() - 1]),
+        Some('<') if s.len() > 1 => Cow::Borrowed(&s[1..s.len() - 1]),
+        _ => Cow::Borrowed(s),
+    }
+}
+
+/// Returns "s" where each ```\``` is replaced by the character following it
+fn unescape(s: &str) -> Cow<'_, str> {
+    if !s.contains('\\') {
+        return Cow::Borrowed(s);
+    }
+    let mut res = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => res.extend(chars.next()),
+            c => res.push(c),
+        }
+    }
+    Cow::Owned(res)
+}
+
+/// Returns the line and column of "span" in "regexp", both starting at 1
+fn line_col(regexp: &str, span: Span) -> (usize, usize) {
+    let before = &regexp[..span.start()];
+    let line = before.matches('\n').count() + 1;
+    let col = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+    (line, col)
+}
+
+impl RegExp<Span> {
+    /// Replaces each span of "regexp" by the symbol returned by "f"
+    fn map_symbols<T>(
+        self,
+        regexp: &str,
+        f: &mut impl FnMut(&str, Span) -> Result<T, String>,
+    ) -> Result<RegExp<T>, String> {
+        Ok(match self {
+            RegExp::Epsilon => RegExp::Epsilon,
+            RegExp::Symbol(span) => RegExp::Symbol(f(&symbol_str(regexp, span), span)?),
+            RegExp::Repeat(c) => RegExp::Repeat(Box::new(c.map_symbols(regexp, f)?)),
+            RegExp::Concat(l, r) => RegExp::Concat(
+                Box::new(l.map_symbols(regexp, f)?),
+                Box::new(r.map_symbols(regexp, f)?),
+            ),
+            RegExp::Or(l, r) => RegExp::Or(
+                Box::new(l.map_symbols(regexp, f)?),
+                Box::new(r.map_symbols(regexp, f)?),
+            ),
+        })
+    }
+}
+
+impl<T> RegExp<T> {
+    /// Parses "regexp" into a regular expression where each symbol is created
+    /// by "symbol" from the text of its token. Quotes and angle brackets
+    /// around multi-character symbols are removed before calling "symbol".
+    ///
+    /// Returns an error describing the syntax error, or the position of the
+    /// first symbol rejected by "symbol" along with its error.
+    pub fn parse_with<E>(
+        regexp: &str,
+        mut symbol: impl FnMut(&str) -> Result<T, E>,
+    ) -> Result<RegExp<T>, String>
+    where
+        E: Display,
+    {
+        parse_spans(regexp)?.map_symbols(regexp, &mut |s, span| {
+            symbol(s).map_err(|e| {
+                let (line, col) = line_col(regexp, span);
+                format!(
+                    "Invalid symbol \"{}\" at line {} column {}: {}\n\
+                    Unable to evaluate expression.",
+                    s, line, col, e
+                )
+            })
+        })
+    }
+
+    /// Parses "regexp" into a regular expression where each symbol is created
+    /// with the [FromStr] implementation of T
+    pub fn parse(regexp: &str) -> Result<RegExp<T>, String>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        Self::parse_with(regexp, T::from_str)
+    }
+}
+
 impl TryFrom<&str> for RegExp<char> {
     type Error = String;
 
     fn try_from(regexp: &str) -> Result<RegExp<char>, Self::Error> {
-        let lexerdef = reg_l::lexerdef();
-        let lexer = lexerdef.lexer(regexp);
-        let (res, errs) = reg_y::parse(&lexer);
-        let mut err = String::new();
-        for e in &errs {
-            err.push_str(&format!("{}\n", e.pp(&lexer, &reg_y::token_epp)));
-        }
-        match res {
-            Some(Ok(r)) if errs.is_empty() => Ok(r),
-            _ => {
-                err.push_str("Unable to evaluate expression.");
-                Err(err)
-            }
-        }
+        RegExp::parse(regexp)
     }
 }
 
@@ -87,20 +212,7 @@ impl TryFrom<String> for RegExp<char> {
     type Error = String;
 
     fn try_from(regexp: String) -> Result<RegExp<char>, Self::Error> {
-        let lexerdef = reg_l::lexerdef();
-        let lexer = lexerdef.lexer(regexp.as_str());
-        let (res, errs) = reg_y::parse(&lexer);
-        let mut err = String::new();
-        for e in &errs {
-            err.push_str(&format!("{}\n", e.pp(&lexer, &reg_y::token_epp)));
-        }
-        match res {
-            Some(Ok(r)) if errs.is_empty() => Ok(r),
-            _ => {
-                err.push_str("Unable to evaluate expression.");
-                Err(err)
-            }
-        }
+        RegExp::parse(regexp.as_str())
     }
 }
 
@@ -251,11 +363,30 @@ where
     }
 }
 
+/// Returns whether "c" can not be written as a symbol without quotes
+fn is_reserved(c: char) -> bool {
+    c.is_whitespace() || "+*().$'<>".contains(c)
+}
+
+/// Writes "symbol" so that it is read back as a single symbol by the parser
+fn write_symbol(f: &mut impl fmt::Write, symbol: &impl Display) -> fmt::Result {
+    let s = symbol.to_string();
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if !is_reserved(c) => write!(f, "{}", c),
+        _ if !s.contains(['\'', '\\']) => write!(f, "'{}'", s),
+        (Some(_), _) if !s.contains(|c: char| c.is_whitespace() || c == '<' || c == '>') => {
+            write!(f, "<{}>", s)
+        }
+        _ => write!(f, "'{}'", s.replace('\\', "\\\\").replace('\'', "\\'")),
+    }
+}
+
 impl<T: Display> Display for RegExp<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Self::Epsilon => write!(f, "$"),
-            Self::Symbol(s) => write!(f, "{}", s),
+            Self::Symbol(s) => write_symbol(f, s),
             Self::Repeat(r) => write!(f, "{}*", r),
             Self::Or(r, l) => write!(f, "({}+{})", r, l),
             Self::Concat(r, l) => write!(f, "({}.{})", r, l),
@@ -311,6 +442,91 @@ mod test {
         assert!(a.is_err())
     }
 
+    #[test]
+    fn unicode() {
+        let a = RegExp::try_from("(é+λ)*.1");
+        assert_eq!(
+            "Ok(Concat(Repeat(Or(Symbol('é'), Symbol('λ'))), Symbol('1')))",
+            format!("{:?}", a)
+        );
+    }
+
+    #[test]
+    fn names() {
+        let a = RegExp::<String>::parse("'title'.<author>*+c");
+        assert_eq!(
+            "Ok(Or(Concat(Symbol(\"title\"), Repeat(Symbol(\"author\"))), \
+            Symbol(\"c\")))",
+            format!("{:?}", a)
+        );
+        let a = a.unwrap();
+        assert_eq!(Ok(a.clone()), RegExp::parse(&a.to_string()));
+    }
+
+    #[test]
+    fn names_round_trip() {
+        let names = ["", "it's here", "'>", "a\\'b c", "\\", "<x y>", "'", "a\\b"];
+        for name in names {
+            let a = RegExp::Concat(
+                Box::new(RegExp::Symbol(String::from(name))),
+                Box::new(RegExp::Symbol(String::from("c"))),
+            );
+            assert_eq!(Ok(a.clone()), RegExp::parse(&a.to_string()), "{}", a);
+        }
+        assert_eq!(
+            "Ok(Symbol(\"it's\"))",
+            format!("{:?}", RegExp::<String>::parse("'it\\'s'"))
+        );
+    }
+
+    #[test]
+    fn error_name() {
+        let a = RegExp::<char>::try_from("a.'bc'");
+        assert!(a.is_err());
+        let a = RegExp::<char>::try_from("a.'+'");
+        assert_eq!("Ok(Concat(Symbol('a'), Symbol('+')))", format!("{:?}", a));
+        assert_eq!("(a.'+')", a.unwrap().to_string());
+    }
+
+    #[test]
+    fn parse_with() {
+        #[derive(Debug, PartialEq)]
+        enum Token {
+            Ident,
+            Number,
+        }
+        let a = RegExp::parse_with("'id'.(<num>+'id')*", |s| match s {
+            "id" => Ok(Token::Ident),
+            "num" => Ok(Token::Number),
+            _ => Err("unknown token"),
+        });
+        assert_eq!(
+            "Ok(Concat(Symbol(Ident), Repeat(Or(Symbol(Number), Symbol(Ident)))))",
+            format!("{:?}", a)
+        );
+        let a = RegExp::parse_with("'id'.\n<str>", |s| match s {
+            "id" => Ok(Token::Ident),
+            _ => Err("unknown token"),
+        });
+        assert_eq!(
+            Err(String::from(
+                "Invalid symbol \"str\" at line 2 column 1: unknown token\n\
+                Unable to evaluate expression."
+            )),
+            a
+        );
+    }
+
+    #[test]
+    fn parse_from_str() {
+        let a = RegExp::<u8>::parse("'12'.3*");
+        assert_eq!(
+            "Ok(Concat(Symbol(12), Repeat(Symbol(3))))",
+            format!("{:?}", a)
+        );
+        assert!(RegExp::<u8>::parse("'256'").is_err());
+    }
+
     #[test]
     fn numbered() {
         let a = RegExp::try_from("(a+b).(a*.b)");
@@ -0,0 +1,148 @@
+//! Module for printing regular expressions with as few parentheses as
+//! possible, according to the precedence and associativity of the operators
+
+use super::{write_symbol, RegExp};
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Spaces written around the binary operators
+pub enum Spacing {
+    /// No space at all, ```a+b.c```
+    #[default]
+    None,
+    /// Spaces around the "or" operator only, ```a + b.c```
+    Or,
+    /// Spaces around both binary operators, ```a + b . c```
+    All,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Options of the pretty printer
+pub struct PrettyOptions {
+    /// Removes the parentheses of chains of the same associative operator
+    /// nested on the right, ```a+(b+c)``` is written ```a+b+c```. The output
+    /// then parses back to an equivalent expression, but not always to an
+    /// identical tree since the parser associates to the left
+    pub flatten: bool,
+    /// Spaces written around the binary operators
+    pub spacing: Spacing,
+}
+
+/// Structure displaying a regular expression with the minimum of parentheses
+pub struct Pretty<'r, T> {
+    regexp: &'r RegExp<T>,
+    options: PrettyOptions,
+}
+
+/// Precedence of the "or" operator
+const OR: u8 = 0;
+/// Precedence of the concatenation operator
+const CONCAT: u8 = 1;
+/// Precedence of the repetition operator
+const REPEAT: u8 = 2;
+/// Precedence of symbols and epsilon
+const ATOM: u8 = 3;
+
+impl<T> RegExp<T> {
+    /// Returns a structure displaying the regular expression with the minimum
+    /// of parentheses. Unless "options.flatten" is ```true```, the output
+    /// parses back to an identical tree
+    pub fn pretty(&self, options: PrettyOptions) -> Pretty<'_, T> {
+        Pretty {
+            regexp: self,
+            options,
+        }
+    }
+
+    /// Returns the precedence of the root operator of the expression
+    fn precedence(&self) -> u8 {
+        match self {
+            RegExp::Epsilon | RegExp::Symbol(_) => ATOM,
+            RegExp::Repeat(_) => REPEAT,
+            RegExp::Concat(_, _) => CONCAT,
+            RegExp::Or(_, _) => OR,
+        }
+    }
+}
+
+impl<'r, T: Display> Pretty<'r, T> {
+    /// Writes "regexp", surrounded by parentheses if the precedence of its
+    /// root operator is less than "min"
+    fn write(&self, f: &mut Formatter<'_>, regexp: &RegExp<T>, min: u8) -> fmt::Result {
+        let paren = regexp.precedence() < min;
+        if paren {
+            write!(f, "(")?;
+        }
+        match regexp {
+            RegExp::Epsilon => write!(f, "$")?,
+            RegExp::Symbol(s) => write_symbol(f, s)?,
+            RegExp::Repeat(c) => {
+                self.write(f, c, REPEAT)?;
+                write!(f, "*")?;
+            }
+            RegExp::Concat(l, r) => {
+                self.write(f, l, CONCAT)?;
+                match self.options.spacing {
+                    Spacing::All => write!(f, " . ")?,
+                    _ => write!(f, ".")?,
+                }
+                self.write(f, r, if self.options.flatten { CONCAT } else { REPEAT })?;
+            }
+            RegExp::Or(l, r) => {
+                self.write(f, l, OR)?;
+                match self.options.spacing {
+                    Spacing::None => write!(f, "+")?,
+                    _ => write!(f, " + ")?,
+                }
+                self.write(f, r, if self.options.flatten { OR } else { CONCAT })?;
+            }
+        }
+        if paren {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'r, T: Display> Display for Pretty<'r, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write(f, self.regexp, OR)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PrettyOptions, Spacing};
+    use crate::regexp::RegExp;
+
+    #[test]
+    fn minimal() {
+        let a = RegExp::try_from("(((a+b)*.a).b)+($.(c.d))").unwrap();
+        assert_eq!(
+            "(a+b)*.a.b+$.(c.d)",
+            a.pretty(PrettyOptions::default()).to_string()
+        );
+        let a = RegExp::try_from("a+(b+c)**").unwrap();
+        assert_eq!("a+(b+c)**", a.pretty(PrettyOptions::default()).to_string());
+    }
+
+    #[test]
+    fn flatten() {
+        let a = RegExp::try_from("(a+(b+c)).(d.(e.f))").unwrap();
+        let options = PrettyOptions {
+            flatten: true,
+            spacing: Spacing::None,
+        };
+        assert_eq!("(a+b+c).d.e.f", a.pretty(options).to_string());
+    }
+
+    #[test]
+    fn spacing() {
+        let a = RegExp::try_from("a.b+c*").unwrap();
+        let mut options = PrettyOptions::default();
+        options.spacing = Spacing::Or;
+        assert_eq!("a.b + c*", a.pretty(options).to_string());
+        options.spacing = Spacing::All;
+        assert_eq!("a . b + c*", a.pretty(options).to_string());
+    }
+}
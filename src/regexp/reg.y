@@ -1,20 +1,20 @@
 %start Expr
 %avoid_insert "SYMBOL"
+%avoid_insert "NAME"
 %avoid_insert "EPSILON"
 %left '+'
 %left '.'
 %left '*'
 %%
-Expr -> Result<RegExp<char>, ()>:
+Expr -> Result<RegExp<Span>, ()>:
     Expr '*' { Ok(RegExp::Repeat(Box::new($1?))) }
     | Expr '+' Expr { Ok(RegExp::Or(Box::new($1?), Box::new($3?))) }
     | Expr '.' Expr {  Ok(RegExp::Concat(Box::new($1?), Box::new($3?))) }
     | '(' Expr ')' { Ok($2?) } 
     | 'EPSILON' { Ok(RegExp::Epsilon) }
-    | 'SYMBOL' { 
-        let v = $1.map_err(|_| ())?;
-        Ok(RegExp::Symbol($lexer.span_str(v.span()).chars().next().ok_or(())?))       
-    } 
+    | 'SYMBOL' { Ok(RegExp::Symbol($1.map_err(|_| ())?.span())) }
+    | 'NAME' { Ok(RegExp::Symbol($1.map_err(|_| ())?.span())) }
     ;
 %%
-use crate::regexp::RegExp;
\ No newline at end of file
+use crate::regexp::RegExp;
+use lrpar::Span;
\ No newline at end of file
@@ -0,0 +1,184 @@
+//! Module for the algebraic simplification of regular expressions, using the
+//! identities of Kleene algebra
+
+use super::RegExp;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Rewrite rules applied by [RegExp::simplify]
+pub enum Rewrite {
+    /// ```$.e = e``` and ```e.$ = e```
+    ConcatEpsilon,
+    /// ```e+e = e```
+    OrIdempotent,
+    /// ```$+e = e``` when ```e``` recognizes the empty word
+    OrEpsilonNullable,
+    /// Flattening and sorting of a chain of "or", using associativity and
+    /// commutativity
+    OrSort,
+    /// ```(e*)* = e*```
+    RepeatRepeat,
+    /// ```$* = $```
+    RepeatEpsilon,
+    /// ```($+e)* = e*```, epsilon is removed under a repetition
+    RepeatOrEpsilon,
+}
+
+impl<T> RegExp<T> {
+    /// Returns whether the empty word is recognized by the regular expression
+    pub fn nullable(&self) -> bool {
+        match self {
+            RegExp::Epsilon | RegExp::Repeat(_) => true,
+            RegExp::Symbol(_) => false,
+            RegExp::Concat(l, r) => l.nullable() && r.nullable(),
+            RegExp::Or(l, r) => l.nullable() || r.nullable(),
+        }
+    }
+
+    /// Consumes the regular expression and pushes the operands of its chain
+    /// of "or" in "terms", from left to right
+    fn into_terms(self, terms: &mut Vec<RegExp<T>>) {
+        match self {
+            RegExp::Or(l, r) => {
+                l.into_terms(terms);
+                r.into_terms(terms);
+            }
+            e => terms.push(e),
+        }
+    }
+
+    /// Returns the chain of "or", associated to the left, of the non empty
+    /// list "terms"
+    fn from_terms(terms: Vec<RegExp<T>>) -> RegExp<T> {
+        let mut terms = terms.into_iter();
+        let first = terms.next().expect("at least one term");
+        terms.fold(first, |acc, e| RegExp::Or(Box::new(acc), Box::new(e)))
+    }
+}
+
+impl<T> RegExp<T>
+where
+    T: Ord + Clone,
+{
+    /// Returns a simplified regular expression recognizing the same language
+    pub fn simplify(&self) -> RegExp<T> {
+        self.simplify_report().0
+    }
+
+    /// Returns a simplified regular expression recognizing the same language,
+    /// accompanied by the list of rewrite rules applied, in application
+    /// order
+    pub fn simplify_report(&self) -> (RegExp<T>, Vec<Rewrite>) {
+        let mut report = Vec::new();
+        let res = self.clone().simplify_with(&mut report);
+        (res, report)
+    }
+
+    /// Auxiliary function of [RegExp::simplify_report]
+    fn simplify_with(self, report: &mut Vec<Rewrite>) -> RegExp<T> {
+        match self {
+            RegExp::Epsilon | RegExp::Symbol(_) => self,
+            RegExp::Repeat(c) => {
+                let mut c = c.simplify_with(report);
+                if let RegExp::Or(_, _) = c {
+                    let mut terms = Vec::new();
+                    c.into_terms(&mut terms);
+                    let len = terms.len();
+                    terms.retain(|e| e != &RegExp::Epsilon);
+                    if terms.len() != len {
+                        report.push(Rewrite::RepeatOrEpsilon);
+                    }
+                    c = RegExp::from_terms(terms);
+                }
+                match c {
+                    RegExp::Epsilon => {
+                        report.push(Rewrite::RepeatEpsilon);
+                        RegExp::Epsilon
+                    }
+                    RegExp::Repeat(_) => {
+                        report.push(Rewrite::RepeatRepeat);
+                        c
+                    }
+                    c => RegExp::Repeat(Box::new(c)),
+                }
+            }
+            RegExp::Concat(l, r) => match (l.simplify_with(report), r.simplify_with(report)) {
+                (RegExp::Epsilon, e) | (e, RegExp::Epsilon) => {
+                    report.push(Rewrite::ConcatEpsilon);
+                    e
+                }
+                (l, r) => RegExp::Concat(Box::new(l), Box::new(r)),
+            },
+            RegExp::Or(l, r) => {
+                let l = l.simplify_with(report);
+                let r = r.simplify_with(report);
+                let chained = !matches!(r, RegExp::Or(_, _));
+                let mut terms = Vec::new();
+                l.into_terms(&mut terms);
+                r.into_terms(&mut terms);
+                if !chained || !terms.windows(2).all(|w| w[0] <= w[1]) {
+                    report.push(Rewrite::OrSort);
+                    terms.sort();
+                }
+                let len = terms.len();
+                terms.dedup();
+                if terms.len() != len {
+                    report.push(Rewrite::OrIdempotent);
+                }
+                if terms.len() > 1
+                    && terms.contains(&RegExp::Epsilon)
+                    && terms.iter().any(|e| e != &RegExp::Epsilon && e.nullable())
+                {
+                    report.push(Rewrite::OrEpsilonNullable);
+                    terms.retain(|e| e != &RegExp::Epsilon);
+                }
+                RegExp::from_terms(terms)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rewrite;
+    use crate::regexp::RegExp;
+
+    #[test]
+    fn concat() {
+        let a = RegExp::try_from("$.a.($.b).$").unwrap();
+        let (s, report) = a.simplify_report();
+        assert_eq!(RegExp::try_from("a.b").unwrap(), s);
+        assert_eq!(vec![Rewrite::ConcatEpsilon; 3], report);
+    }
+
+    #[test]
+    fn or() {
+        let a = RegExp::try_from("b+(a+(b+c))").unwrap();
+        let (s, report) = a.simplify_report();
+        assert_eq!(RegExp::try_from("a+b+c").unwrap(), s);
+        assert_eq!(
+            vec![Rewrite::OrSort, Rewrite::OrSort, Rewrite::OrIdempotent],
+            report
+        );
+        let a = RegExp::try_from("$+a*").unwrap();
+        assert_eq!(RegExp::try_from("a*").unwrap(), a.simplify());
+    }
+
+    #[test]
+    fn repeat() {
+        let a = RegExp::try_from("(($+a)*)*+$*").unwrap();
+        let (s, report) = a.simplify_report();
+        assert_eq!(RegExp::try_from("a*").unwrap(), s);
+        assert!(report.contains(&Rewrite::OrEpsilonNullable));
+        assert!(report.contains(&Rewrite::RepeatOrEpsilon));
+        assert!(report.contains(&Rewrite::RepeatRepeat));
+        assert!(report.contains(&Rewrite::RepeatEpsilon));
+    }
+
+    #[test]
+    fn unchanged() {
+        let a = RegExp::try_from("(a+b)*.a.b").unwrap();
+        let (s, report) = a.simplify_report();
+        assert_eq!(a, s);
+        assert!(report.is_empty());
+    }
+}
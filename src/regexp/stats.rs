@@ -0,0 +1,151 @@
+//! Module for measuring regular expressions
+
+use super::RegExp;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, hash::Hash};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+/// Structure grouping together the measures of a regular expression
+pub struct RegExpStats {
+    /// Number of occurrences of symbols, i.e. the number of positions of the
+    /// Glushkov automaton
+    pub alphabetic_width: usize,
+    /// Maximum number of nested repetitions
+    pub star_height: usize,
+    /// Maximum number of nested operators
+    pub depth: usize,
+    /// Number of operators
+    pub operators_count: usize,
+    /// Number of nodes of the tree, operators, symbols and epsilons included
+    pub size: usize,
+    /// Number of distinct symbols
+    pub symbols_count: usize,
+}
+
+impl<T> RegExp<T> {
+    /// Returns the alphabetic width of the regular expression, i.e. the number
+    /// of occurrences of symbols
+    pub fn alphabetic_width(&self) -> usize {
+        match self {
+            RegExp::Epsilon => 0,
+            RegExp::Symbol(_) => 1,
+            RegExp::Repeat(c) => c.alphabetic_width(),
+            RegExp::Concat(l, r) | RegExp::Or(l, r) => l.alphabetic_width() + r.alphabetic_width(),
+        }
+    }
+
+    /// Returns the star height of the regular expression, i.e. the maximum
+    /// number of nested repetitions
+    pub fn star_height(&self) -> usize {
+        match self {
+            RegExp::Epsilon | RegExp::Symbol(_) => 0,
+            RegExp::Repeat(c) => c.star_height() + 1,
+            RegExp::Concat(l, r) | RegExp::Or(l, r) => l.star_height().max(r.star_height()),
+        }
+    }
+
+    /// Returns the depth of the regular expression, i.e. the maximum number of
+    /// nested operators. A symbol or epsilon alone has a depth of 0
+    pub fn depth(&self) -> usize {
+        match self {
+            RegExp::Epsilon | RegExp::Symbol(_) => 0,
+            RegExp::Repeat(c) => c.depth() + 1,
+            RegExp::Concat(l, r) | RegExp::Or(l, r) => l.depth().max(r.depth()) + 1,
+        }
+    }
+
+    /// Returns the number of operators of the regular expression
+    pub fn operators_count(&self) -> usize {
+        match self {
+            RegExp::Epsilon | RegExp::Symbol(_) => 0,
+            RegExp::Repeat(c) => c.operators_count() + 1,
+            RegExp::Concat(l, r) | RegExp::Or(l, r) => {
+                l.operators_count() + r.operators_count() + 1
+            }
+        }
+    }
+
+    /// Returns the size of the regular expression, i.e. the number of nodes of
+    /// its tree
+    pub fn size(&self) -> usize {
+        match self {
+            RegExp::Epsilon | RegExp::Symbol(_) => 1,
+            RegExp::Repeat(c) => c.size() + 1,
+            RegExp::Concat(l, r) | RegExp::Or(l, r) => l.size() + r.size() + 1,
+        }
+    }
+}
+
+impl<T> RegExp<T>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Returns the set of symbols used by the regular expression
+    pub fn symbols(&self) -> HashSet<T> {
+        let mut set = HashSet::new();
+        self.collect_symbols(&mut set);
+        set
+    }
+
+    /// Auxiliary function of [RegExp::symbols]
+    fn collect_symbols(&self, set: &mut HashSet<T>) {
+        match self {
+            RegExp::Epsilon => {}
+            RegExp::Symbol(s) => {
+                set.insert(s.clone());
+            }
+            RegExp::Repeat(c) => c.collect_symbols(set),
+            RegExp::Concat(l, r) | RegExp::Or(l, r) => {
+                l.collect_symbols(set);
+                r.collect_symbols(set);
+            }
+        }
+    }
+
+    /// Returns all the measures of the regular expression
+    pub fn stats(&self) -> RegExpStats {
+        RegExpStats {
+            alphabetic_width: self.alphabetic_width(),
+            star_height: self.star_height(),
+            depth: self.depth(),
+            operators_count: self.operators_count(),
+            size: self.size(),
+            symbols_count: self.symbols().len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RegExpStats;
+    use crate::regexp::RegExp;
+
+    #[test]
+    fn stats() {
+        let a = RegExp::try_from("(a+b*)*.a.(c+$)").unwrap();
+        assert_eq!(
+            RegExpStats {
+                alphabetic_width: 4,
+                star_height: 2,
+                depth: 5,
+                operators_count: 6,
+                size: 11,
+                symbols_count: 3,
+            },
+            a.stats()
+        );
+        assert_eq!(a.alphabetic_width(), a.linearization_start(1).1 - 1);
+    }
+
+    #[test]
+    fn leaf() {
+        let a = RegExp::<char>::Epsilon;
+        assert_eq!(
+            RegExpStats {
+                size: 1,
+                ..Default::default()
+            },
+            a.stats()
+        );
+    }
+}
@@ -1,5 +1,11 @@
 use glushkovizer::prelude::*;
-use glushkovizer::{automata::Automata, regexp::RegExp};
+use glushkovizer::{
+    automata::{
+        AmbiguityDegree, Automata, AutomataError, BitGlushkov, DenseDfa, Document, DotOptions,
+        Outcome, RankDir, Rejection, SearchMode, FORMAT_VERSION, JSON_SCHEMA,
+    },
+    regexp::RegExp,
+};
 use rand::Rng;
 use std::{array, usize};
 
@@ -7,6 +13,7 @@ const MAX_DEPTH: usize = 4;
 const NB_WORD: usize = 100;
 const NB_TEST: usize = 10;
 const NB_REPEAT: usize = 10;
+const NB_REGEX: usize = 100;
 
 #[test]
 fn automata() {
@@ -22,6 +29,849 @@ fn automata() {
     }
 }
 
+#[test]
+fn simplify() {
+    for i in 0..NB_TEST {
+        let r = gen_regex(MAX_DEPTH);
+        let s = r.simplify();
+        assert_eq!(s, s.simplify());
+        assert!(s.size() <= r.size());
+        let a = Automata::from(r.clone());
+        let b = Automata::from(s.clone());
+        let w: [String; NB_WORD] = gen_words(&r);
+        let ws: [String; NB_WORD] = gen_words(&s);
+        for word in w {
+            if !b.accept(word.chars().collect::<Vec<char>>().iter()) {
+                panic!("Error on {}:\n{}\n{}\n{}", i, word, r, s);
+            }
+        }
+        for word in ws {
+            if !a.accept(word.chars().collect::<Vec<char>>().iter()) {
+                panic!("Error on {}:\n{}\n{}\n{}", i, word, r, s);
+            }
+        }
+    }
+}
+
+#[test]
+fn one_unambiguous() {
+    for _ in 0..NB_REGEX {
+        let r = gen_regex(MAX_DEPTH);
+        let res = r.is_one_unambiguous();
+        let a = Automata::from(r);
+        assert_eq!(res.is_ok(), deterministic(&a));
+        if let Err(c) = res {
+            assert_eq!(c.first.0, c.second.0);
+            assert!(c.first.1 < c.second.1);
+        }
+    }
+}
+
+#[test]
+fn determinize() {
+    for i in 0..NB_TEST {
+        let r = gen_regex(MAX_DEPTH);
+        let a = Automata::from(r.clone());
+        let d = a.determinize();
+        let m = a.minimize();
+        assert!(deterministic(&d));
+        assert!(deterministic(&m));
+        assert!(m.states_count() <= d.states_count());
+        let w: [String; NB_WORD] = gen_words(&r);
+        for word in w {
+            let word = word.chars().collect::<Vec<char>>();
+            if !d.accept(word.iter()) || !m.accept(word.iter()) {
+                panic!("Error on {}:\n{:?}\n{}", i, word, r);
+            }
+        }
+    }
+}
+
+#[test]
+fn bkw() {
+    for i in 0..NB_REGEX {
+        let r = gen_regex(MAX_DEPTH);
+        let d = r.to_one_unambiguous();
+        if r.is_one_unambiguous().is_ok() {
+            assert!(d.is_some());
+        }
+        if let Some(d) = d {
+            assert_eq!(Ok(()), d.is_one_unambiguous());
+            let a = Automata::from(r.clone()).minimize();
+            let b = Automata::from(d.clone()).minimize();
+            assert_eq!(a.states_count(), b.states_count());
+            let w: [String; NB_WORD] = gen_words(&r);
+            let wd: [String; NB_WORD] = gen_words(&d);
+            for word in w.iter().chain(wd.iter()) {
+                let word = word.chars().collect::<Vec<char>>();
+                if a.accept(word.iter()) != b.accept(word.iter()) {
+                    panic!("Error on {}:\n{:?}\n{}\n{}", i, word, r, d);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn bkw_sequence() {
+    let r = RegExp::try_from(vec!["(a+b)"; 40].join(".").as_str()).unwrap();
+    let d = r.to_one_unambiguous().unwrap();
+    assert_eq!(Ok(()), d.is_one_unambiguous());
+    assert_eq!(
+        Automata::from(r.clone()).minimize().states_count(),
+        Automata::from(d.clone()).minimize().states_count()
+    );
+    assert_eq!(r.to_string().len(), d.to_string().len());
+}
+
+#[test]
+fn ambiguity() {
+    for i in 0..NB_REGEX {
+        let r = gen_regex(MAX_DEPTH);
+        let a = Automata::from(r.clone());
+        let word = a.ambiguous_word();
+        assert_eq!(word.is_none(), a.is_unambiguous());
+        assert_eq!(
+            a.is_unambiguous(),
+            a.ambiguity_degree() == AmbiguityDegree::Unambiguous
+        );
+        if deterministic(&a) {
+            assert!(a.is_unambiguous());
+        }
+        if let Some(word) = word {
+            if !a.accept(word.iter()) {
+                panic!("Error on {}:\n{:?}\n{}", i, word, r);
+            }
+        }
+        if let Err(word) = r.is_strongly_unambiguous() {
+            if !a.accept(word.iter()) {
+                panic!("Error on {}:\n{:?}\n{}", i, word, r);
+            }
+        }
+    }
+}
+
+#[test]
+fn ambiguity_degree() {
+    let a: Automata<char, usize> = Automata::new();
+    a.add_state(0);
+    a.add_state(1);
+    a.add_input(&0).unwrap();
+    a.add_output(&1).unwrap();
+    a.add_transition(&0, &0, 'a').unwrap();
+    a.add_transition(&0, &1, 'a').unwrap();
+    a.add_transition(&1, &1, 'a').unwrap();
+    assert_eq!(AmbiguityDegree::Polynomial, a.ambiguity_degree());
+    a.remove_transition(&1, &1, &'a').unwrap();
+    assert_eq!(AmbiguityDegree::Unambiguous, a.ambiguity_degree());
+    a.add_state(2);
+    a.add_output(&2).unwrap();
+    a.add_transition(&0, &2, 'a').unwrap();
+    assert_eq!(AmbiguityDegree::Finite, a.ambiguity_degree());
+    assert_eq!(Some(vec!['a']), a.ambiguous_word());
+    let a = Automata::from(RegExp::try_from("(a+a)*").unwrap());
+    assert_eq!(AmbiguityDegree::Exponential, a.ambiguity_degree());
+    let a = Automata::from(RegExp::try_from("(a+a).b.b.b.b+c.(d+d)").unwrap());
+    assert_eq!(Some(vec!['c', 'd']), a.ambiguous_word());
+}
+
+#[test]
+fn run() {
+    for i in 0..NB_TEST {
+        let r = gen_regex(MAX_DEPTH);
+        let w: [String; NB_WORD] = gen_words(&r);
+        let a = Automata::from(r.clone());
+        for word in w {
+            let word = word.chars().collect::<Vec<char>>();
+            let run = a.run(word.iter());
+            assert_eq!(word.len() + 1, run.steps.len());
+            match run.outcome {
+                Outcome::Accepted { path, symbols } => {
+                    assert_eq!(word, symbols);
+                    assert!(a.inputs().contains(&path[0]));
+                    assert!(a.outputs().contains(&path[word.len()]));
+                    for (j, symbol) in symbols.iter().enumerate() {
+                        assert!(a
+                            .get_follow(&path[j], symbol)
+                            .unwrap()
+                            .contains(&path[j + 1]));
+                    }
+                }
+                Outcome::Rejected(_) => panic!("Error on {}:\n{:?}\n{}", i, word, r),
+            }
+        }
+    }
+}
+
+#[test]
+fn rejection() {
+    let a = Automata::from(RegExp::try_from("a.(b.c)*").unwrap());
+    let word: Vec<char> = "abcbd".chars().collect();
+    let run = a.run(word.iter());
+    assert_eq!(6, run.steps.len());
+    assert!(run.steps[5].is_empty());
+    assert_eq!(
+        Outcome::Rejected(Rejection {
+            longest_accepted_prefix: Some(3),
+            dead_at: Some(4),
+            allowed: vec!['c'],
+        }),
+        run.outcome
+    );
+    let word: Vec<char> = "ab".chars().collect();
+    assert_eq!(
+        Outcome::Rejected(Rejection {
+            longest_accepted_prefix: Some(1),
+            dead_at: None,
+            allowed: vec!['c'],
+        }),
+        a.run(word.iter()).outcome
+    );
+    let word: Vec<char> = "b".chars().collect();
+    assert_eq!(
+        Outcome::Rejected(Rejection {
+            longest_accepted_prefix: None,
+            dead_at: Some(0),
+            allowed: vec!['a'],
+        }),
+        a.run(word.iter()).outcome
+    );
+}
+
+#[test]
+fn matcher() {
+    for i in 0..NB_TEST {
+        let r = gen_regex(MAX_DEPTH);
+        let w: [String; NB_WORD] = gen_words(&r);
+        let a = Automata::from(r.clone());
+        let mut m = a.matcher();
+        for word in w {
+            m.reset();
+            for symbol in word.chars() {
+                assert!(m.allowed_next().contains(&symbol));
+                if !m.feed(&symbol) {
+                    panic!("Error on {}:\n{}\n{}", i, word, r);
+                }
+            }
+            assert!(m.is_accepting());
+        }
+    }
+    let a = Automata::from(RegExp::try_from("a.(b.c)*").unwrap());
+    let mut m = a.matcher();
+    assert_eq!(vec!['a'], m.allowed_next());
+    assert!(m.feed_all("abc".chars().collect::<Vec<char>>().iter()));
+    assert!(m.is_accepting());
+    assert!(m.feed(&'b'));
+    assert!(!m.is_accepting());
+    assert_eq!(vec!['c'], m.allowed_next());
+    assert!(!m.feed(&'b'));
+    assert!(m.is_dead());
+    assert!(m.allowed_next().is_empty());
+    m.reset();
+    assert!(!m.is_dead());
+    assert!(m.feed(&'a'));
+    assert!(m.is_accepting());
+}
+
+#[test]
+fn search() {
+    for i in 0..NB_TEST {
+        let r = gen_regex(MAX_DEPTH);
+        let w: [String; 3] = gen_words(&r);
+        let a = Automata::from(r.clone());
+        let text: Vec<char> = format!("z{}z{}zz{}", w[0], w[1], w[2])
+            .chars()
+            .take(40)
+            .collect();
+        let all = a.find_all(&text, SearchMode::All);
+        let mut expected = Vec::new();
+        for start in 0..=text.len() {
+            for end in start..=text.len() {
+                if a.accept(text[start..end].iter()) {
+                    expected.push(start..end);
+                }
+            }
+        }
+        if all != expected {
+            panic!("Error on {}:\n{:?}\n{}", i, text, r);
+        }
+        let mut expected = Vec::new();
+        let mut start = 0;
+        while start <= text.len() {
+            match all.iter().filter(|o| o.start == start).last() {
+                Some(o) => {
+                    expected.push(o.clone());
+                    start = o.end.max(start + 1);
+                }
+                None => start += 1,
+            }
+        }
+        if a.find_all(&text, SearchMode::LeftmostLongest) != expected {
+            panic!("Error on {}:\n{:?}\n{}", i, text, r);
+        }
+    }
+    let a = Automata::from(RegExp::try_from("a.b*").unwrap());
+    let text: Vec<char> = "cabbab".chars().collect();
+    assert_eq!(
+        vec![1..4, 4..6],
+        a.find_all(&text, SearchMode::LeftmostLongest)
+    );
+    assert_eq!(
+        vec![1..2, 1..3, 1..4, 4..5, 4..6],
+        a.find_all(&text, SearchMode::All)
+    );
+    assert_eq!(Some(1..4), a.find(&text));
+}
+
+#[test]
+fn bitglushkov() {
+    for i in 0..NB_TEST {
+        let r = gen_regex(MAX_DEPTH + 2);
+        let w: [String; NB_WORD] = gen_words(&r);
+        let a = Automata::from(r.clone());
+        let b = BitGlushkov::from(&r);
+        let c = BitGlushkov::try_from(&a).unwrap();
+        assert_eq!(a.states_count(), b.states_count());
+        for word in w.iter() {
+            let word = word.chars().collect::<Vec<char>>();
+            if !b.accept(word.iter()) || !c.accept(word.iter()) {
+                panic!("Error on {}:\n{:?}\n{}", i, word, r);
+            }
+        }
+        let text: Vec<char> = format!("z{}z{}zz{}", w[0], w[1], w[2])
+            .chars()
+            .take(40)
+            .collect();
+        for mode in [SearchMode::All, SearchMode::LeftmostLongest] {
+            assert_eq!(a.find_all(&text, mode), b.find_all(&text, mode));
+        }
+        assert_eq!(a.find(&text), b.find(&text));
+    }
+    let r = RegExp::try_from("(a.b.c.d.e.f.g.h)*.(a+b+c+d+e+f+g+h)*.z").unwrap();
+    let r = RegExp::Concat(Box::new(r.clone()), Box::new(r));
+    let r = RegExp::Concat(Box::new(r.clone()), Box::new(r));
+    let r = RegExp::Concat(Box::new(r.clone()), Box::new(r));
+    let b = BitGlushkov::from(&r);
+    assert!(b.states_count() > 2 * 64);
+    let a = Automata::from(r);
+    for word in [
+        "zzzz",
+        "abcdefghzzzhgz",
+        "abcdefghabcdefghaazabzabcdefghzz",
+        "abz",
+    ] {
+        let word = word.chars().collect::<Vec<char>>();
+        assert_eq!(a.accept(word.iter()), b.accept(word.iter()));
+    }
+    let b = BitGlushkov::from(&RegExp::try_from("a*.b").unwrap());
+    let mut text = vec!['a'; 100_000];
+    assert_eq!(None, b.find(&text));
+    assert!(b.find_all(&text, SearchMode::LeftmostLongest).is_empty());
+    text.push('b');
+    assert_eq!(Some(0..100_001), b.find(&text));
+    assert_eq!(
+        vec![0..100_001],
+        b.find_all(&text, SearchMode::LeftmostLongest)
+    );
+    let a = Automata::from(RegExp::try_from("a.b").unwrap()).determinize();
+    a.add_transition(&0, &1, 'b').unwrap();
+    assert!(BitGlushkov::try_from(&a).is_err());
+}
+
+#[test]
+fn compile() {
+    for i in 0..NB_TEST {
+        let r = gen_regex(MAX_DEPTH);
+        let w: [String; NB_WORD] = gen_words(&r);
+        let a = Automata::from(r.clone());
+        let d = a.compile();
+        assert_eq!(a.minimize().states_count(), d.states_count());
+        let json = serde_json::to_string(&d).unwrap();
+        assert_eq!(d, serde_json::from_str(&json).unwrap());
+        let cbor = serde_cbor::to_vec(&d).unwrap();
+        assert_eq!(d, serde_cbor::from_slice(&cbor).unwrap());
+        for word in w {
+            let word = word.chars().collect::<Vec<char>>();
+            if !d.accept(word.iter()) {
+                panic!("Error on {}:\n{:?}\n{}", i, word, r);
+            }
+        }
+    }
+    let d = Automata::from(RegExp::try_from("(a+b)*.a").unwrap()).compile();
+    assert_eq!(&['a', 'b'], d.symbols());
+    assert_eq!(2, d.states_count());
+    assert!(d.accept("aba".chars().collect::<Vec<char>>().iter()));
+    assert!(!d.accept("abc".chars().collect::<Vec<char>>().iter()));
+    assert!(DenseDfa::from_raw_parts(vec!['b', 'a'], vec![0, 0], vec![true]).is_err());
+    assert!(DenseDfa::from_raw_parts(vec!['a'], vec![1], vec![true]).is_err());
+    assert!(DenseDfa::from_raw_parts(vec!['a'], vec![DenseDfa::<char>::DEAD], vec![true]).is_ok());
+    assert!(serde_json::from_str::<DenseDfa<char>>(
+        r#"{"symbols":["a"],"table":[3],"finals":[true]}"#
+    )
+    .is_err());
+}
+
+#[test]
+fn to_rust() {
+    let r = RegExp::try_from("(a+b)*.a").unwrap();
+    let expected = "/// Returns whether \"word\" is recognized
+/// Generated by glushkovizer, do not edit
+pub fn ends_with_a(word: &str) -> bool {
+    let mut state: u32 = 0;
+    for symbol in word.chars() {
+        state = match (state, symbol) {
+            (0, 'b') => 0,
+            (0, 'a') => 1,
+            (1, 'b') => 0,
+            (1, 'a') => 1,
+            _ => return false,
+        };
+    }
+    matches!(state, 1)
+}
+";
+    assert_eq!(expected, r.to_rust("ends_with_a").unwrap());
+    let a = Automata::from(r);
+    assert_eq!(expected, a.to_rust("ends_with_a").unwrap());
+    assert_eq!(expected, a.compile().to_rust("ends_with_a").unwrap());
+    let r = RegExp::try_from("<'>").unwrap();
+    assert!(r.to_rust("quote").unwrap().contains("(0, '\\'') => 1,"));
+    let a: Automata<char, usize> = Automata::new();
+    a.add_state(0);
+    a.add_initial(&0).unwrap();
+    assert!(a
+        .to_rust("empty")
+        .unwrap()
+        .contains("pub fn empty(_word: &str) -> bool {\n    false\n}"));
+    let code = RegExp::try_from("$").unwrap().to_rust("epsilon").unwrap();
+    assert!(code.contains("pub fn epsilon(word: &str) -> bool {\n    word.is_empty()\n}"));
+    for name in [
+        "f(x: u8) {} fn g",
+        "my fn",
+        "fn",
+        "",
+        "1a",
+        "r#self",
+        "_",
+        "r#",
+    ] {
+        assert!(matches!(
+            r.to_rust(name),
+            Err(AutomataError::InvalidIdentifier(n)) if n == name
+        ));
+    }
+    for name in ["_private", "été", "r#match"] {
+        assert!(r.to_rust(name).is_ok());
+    }
+    for code in [
+        code,
+        r.to_rust("quote").unwrap(),
+        r.to_rust("r#match").unwrap(),
+    ] {
+        assert!(compiles(&code), "{}", code);
+    }
+}
+
+#[test]
+fn dot_options() {
+    let a = Automata::from(RegExp::try_from("(a+b)*.a").unwrap()).minimize();
+    assert_eq!(
+        a.to_dot(false).unwrap(),
+        a.to_dot_with(&DotOptions::default()).unwrap()
+    );
+    assert_eq!(
+        a.to_dot(true).unwrap(),
+        a.to_dot_with(&DotOptions {
+            color: Some(String::from("white")),
+            ..Default::default()
+        })
+        .unwrap()
+    );
+    let dot = a
+        .to_dot_with(&DotOptions {
+            rankdir: RankDir::TopBottom,
+            font: String::from("Helvetica"),
+            clusters: false,
+            doors: None,
+            initial_arrows: true,
+            merge_edges: true,
+            value_ids: true,
+            highlight_states: vec![1],
+            highlight_edges: vec![(0, 'a', 1)],
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(dot.contains("rankdir=TB"));
+    assert!(dot.contains("node [fontname=\"Helvetica\"];"));
+    assert!(!dot.contains("subgraph"));
+    assert!(!dot.contains("purple"));
+    assert!(!dot.contains("diamond"));
+    assert!(dot.contains("\t__start0 [shape=point]\n\t__start0 -> \"0\"\n"));
+    assert!(
+        dot.contains("\t\"1\" [label = \"1\" peripheries=2 style=filled fillcolor=\"orange\"]\n")
+    );
+    assert!(dot.contains("\t\"0\" -> \"1\" [label = \"a\" color=\"orange\" penwidth=2]\n"));
+    assert!(
+        dot.contains("\t\"1\" -> \"1\" [label = \"a\"]\n")
+            && dot.contains("\t\"1\" -> \"0\" [label = \"b\"]\n")
+    );
+    let a: Automata<char, usize> = Automata::new();
+    a.add_state(0);
+    a.add_transition(&0, &0, 'a').unwrap();
+    a.add_transition(&0, &0, 'b').unwrap();
+    let dot = a
+        .to_dot_with(&DotOptions {
+            merge_edges: true,
+            value_ids: true,
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(
+        dot.contains("\t\"0\" -> \"0\" [label = \"a,b\"]\n")
+            || dot.contains("\t\"0\" -> \"0\" [label = \"b,a\"]\n")
+    );
+    let a: Automata<char, String> = Automata::new();
+    a.add_state(String::from("q\\0"));
+    a.add_transition(&String::from("q\\0"), &String::from("q\\0"), '"')
+        .unwrap();
+    let dot = a
+        .to_dot_with(&DotOptions {
+            font: String::from("DejaVu Sans"),
+            background: String::from("#ff8800"),
+            color: Some(String::from("#000000")),
+            highlight_states: vec![String::from("q\\0")],
+            highlight_color: String::from("#00ff00"),
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(dot.contains("\tbgcolor=\"#ff8800\"\n\tnode [fontname=\"DejaVu Sans\"];"));
+    assert!(dot.contains("\tnode [color=\"#000000\", fontcolor=\"#000000\"];"));
+    assert!(dot.contains("[label = \"q\\\\0\" style=filled fillcolor=\"#00ff00\"]"));
+    assert!(dot.contains("\t0 -> 0 [label = \"\\\"\"]\n"));
+}
+
+#[test]
+fn tikz() {
+    for _ in 0..NB_TEST {
+        let a = Automata::from(gen_regex(MAX_DEPTH));
+        let tikz = a.to_tikz(false).unwrap();
+        assert!(tikz.starts_with("\\begin{tikzpicture}"));
+        assert!(tikz.ends_with("\\end{tikzpicture}\n"));
+        assert_eq!(a.states_count(), tikz.matches("\\node[state").count());
+        assert_eq!(a.inputs_count(), tikz.matches("state, initial").count());
+        assert!(!tikz.contains("draw="));
+    }
+    let a = Automata::from(RegExp::try_from("(a.b)*.c").unwrap());
+    assert_eq!(
+        "\\begin{tikzpicture}[->, >=latex, auto, semithick, initial text={}]
+\t\\node[state, initial, draw=purple] (q0) at (0, 0) {$0$};
+\t\\node[state, draw=red] (q1) at (2.5, 0.75) {$1$};
+\t\\node[state, draw=blue] (q2) at (5, 0) {$2$};
+\t\\node[state, accepting, draw=purple] (q3) at (2.5, -0.75) {$3$};
+\t\\path (q0) edge node {$a$} (q1);
+\t\\path (q0) edge node {$c$} (q3);
+\t\\path (q1) edge node {$b$} (q2);
+\t\\path (q2) edge [bend left] node {$a$} (q1);
+\t\\path (q2) edge [bend left] node {$c$} (q3);
+\\end{tikzpicture}
+",
+        a.to_tikz(true).unwrap()
+    );
+}
+
+#[test]
+fn mermaid_graphml() {
+    for _ in 0..NB_TEST {
+        let a = Automata::from(gen_regex(MAX_DEPTH));
+        let transitions: usize = a
+            .states()
+            .iter()
+            .flat_map(|s| a.get_follows(s).unwrap())
+            .map(|(_, l)| l.len())
+            .sum();
+        let mermaid = a.to_mermaid().unwrap();
+        assert_eq!(a.inputs_count(), mermaid.matches("[*] -->").count());
+        assert_eq!(a.outputs_count(), mermaid.matches("--> [*]").count());
+        let graphml = a.to_graphml().unwrap();
+        assert_eq!(a.states_count(), graphml.matches("<node ").count());
+        assert_eq!(transitions, graphml.matches("<edge ").count());
+        assert_eq!(
+            a.inputs_count(),
+            graphml.matches("<data key=\"initial\">true</data>").count()
+        );
+        assert_eq!(
+            a.outputs_count(),
+            graphml.matches("<data key=\"final\">true</data>").count()
+        );
+        let scc = a.kosaraju().len();
+        assert!(graphml.contains(&format!("<data key=\"scc\">{}</data>", scc - 1)));
+        assert!(!graphml.contains(&format!("<data key=\"scc\">{}</data>", scc)));
+    }
+    let a = Automata::from(RegExp::try_from("(a.b)*.c").unwrap());
+    assert_eq!(
+        "stateDiagram-v2
+\tdirection LR
+\ts0 : 0
+\ts1 : 1
+\ts2 : 2
+\ts3 : 3
+\t[*] --> s0
+\ts0 --> s1 : a
+\ts0 --> s3 : c
+\ts1 --> s2 : b
+\ts2 --> s1 : a
+\ts2 --> s3 : c
+\ts3 --> [*]
+",
+        a.to_mermaid().unwrap()
+    );
+    let graphml = a.to_graphml().unwrap();
+    assert!(graphml.contains(
+        "\t\t<node id=\"n1\">
+\t\t\t<data key=\"label\">1</data>
+\t\t\t<data key=\"initial\">false</data>
+\t\t\t<data key=\"final\">false</data>
+\t\t\t<data key=\"scc\">1</data>
+\t\t\t<data key=\"door\">in</data>
+\t\t</node>"
+    ));
+    assert!(graphml.contains(
+        "\t\t<edge source=\"n2\" target=\"n1\">
+\t\t\t<data key=\"symbol\">a</data>
+\t\t</edge>"
+    ));
+}
+
+#[test]
+fn svg() {
+    for _ in 0..NB_TEST {
+        let a = Automata::from(gen_regex(MAX_DEPTH));
+        let svg = a.to_svg(true).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(
+            a.states_count() + a.outputs_count(),
+            svg.matches("<circle ").count()
+        );
+        assert_eq!(a.inputs_count(), svg.matches("<line ").count());
+        let edges: usize = a
+            .states()
+            .iter()
+            .map(|from| {
+                a.states()
+                    .iter()
+                    .filter(|to| !a.get_transition(from, to).unwrap().is_empty())
+                    .count()
+            })
+            .sum();
+        assert_eq!(edges, svg.matches("\t<path ").count());
+        let orbits = a.extract_scc().into_iter().filter(|s| s.is_orbit()).count();
+        assert_eq!(orbits, svg.matches("<rect ").count());
+        assert_eq!(0, a.to_svg(false).unwrap().matches("<rect ").count());
+    }
+}
+
+#[test]
+fn serialization() {
+    for _ in 0..NB_TEST {
+        let r = gen_regex(MAX_DEPTH);
+        let a = Automata::from(r.clone());
+        let json = serde_json::to_string(&a).unwrap();
+        let b: Automata<char, usize> = serde_json::from_str(&json).unwrap();
+        assert_eq!(a.to_dot(false).unwrap(), b.to_dot(false).unwrap());
+        let cbor = serde_cbor::to_vec(&a).unwrap();
+        let b: Automata<char, usize> = serde_cbor::from_slice(&cbor).unwrap();
+        assert_eq!(a.to_dot(false).unwrap(), b.to_dot(false).unwrap());
+        let d = Document::from(r);
+        let json = serde_json::to_string(&d).unwrap();
+        let e: Document<char, usize> = serde_json::from_str(&json).unwrap();
+        assert_eq!(d.metadata, e.metadata);
+        assert_eq!(a.to_dot(false).unwrap(), e.automata.to_dot(false).unwrap());
+        let b: Automata<char, usize> = serde_json::from_str(&json).unwrap();
+        assert_eq!(a.to_dot(false).unwrap(), b.to_dot(false).unwrap());
+    }
+    let legacy = r#"{"states":[0,1],"inputs":[0],"outputs":[1],"follows":[[0,"a",1]]}"#;
+    let a: Automata<char, usize> = serde_json::from_str(legacy).unwrap();
+    assert_eq!(
+        format!(
+            r#"{{"states":[0,1],"inputs":[0],"outputs":[1],"follows":[[0,"a",1]],"version":{}}}"#,
+            FORMAT_VERSION
+        ),
+        serde_json::to_string(&a).unwrap()
+    );
+    let positional = r#"[[0,1],[0],[1],[[0,"a",1]]]"#;
+    let b: Automata<char, usize> = serde_json::from_str(positional).unwrap();
+    assert_eq!(a.to_dot(false).unwrap(), b.to_dot(false).unwrap());
+    let b: Automata<char, usize> =
+        serde_json::from_str(r#"[[0,1],[0],[1],[[0,"a",1]],1]"#).unwrap();
+    assert_eq!(a.to_dot(false).unwrap(), b.to_dot(false).unwrap());
+    assert!(
+        serde_json::from_str::<Automata<char, usize>>(r#"[[0,1],[0],[1],[[0,"a",1]],2]"#).is_err()
+    );
+    let cbor = serde_cbor::to_vec(&(vec![0, 1], vec![0], vec![1], vec![(0, 'a', 1)])).unwrap();
+    let b: Automata<char, usize> = serde_cbor::from_slice(&cbor).unwrap();
+    assert_eq!(a.to_dot(false).unwrap(), b.to_dot(false).unwrap());
+    let d: Document<char, usize> = serde_json::from_str(legacy).unwrap();
+    assert_eq!(None, d.alphabet);
+    assert_eq!(None, d.metadata.name);
+    assert!(d.metadata.labels.is_empty());
+
+    let mut d = Document::from(RegExp::try_from("a.b*").unwrap());
+    d.alphabet = Some(vec!['a', 'b', 'c']);
+    d.metadata.name = Some(String::from("ab"));
+    d.metadata.labels = vec![(0, String::from("start"))];
+    let json = serde_json::to_string(&d).unwrap();
+    assert_eq!(
+        r#"{"states":[0,1,2],"inputs":[0],"outputs":[1,2],"follows":[[0,"a",1],[1,"b",2],[2,"b",2]],"version":1,"alphabet":["a","b","c"],"name":"ab","regex":"(a.b*)","labels":[[0,"start"]]}"#,
+        json
+    );
+    let e: Document<char, usize> = serde_json::from_str(&json).unwrap();
+    assert_eq!(d.alphabet, e.alphabet);
+    assert_eq!(d.metadata, e.metadata);
+    let e: Document<char, usize> =
+        serde_cbor::from_slice(&serde_cbor::to_vec(&d).unwrap()).unwrap();
+    assert_eq!(d.metadata, e.metadata);
+
+    let error = |json: &str| {
+        serde_json::from_str::<Document<char, usize>>(json)
+            .unwrap_err()
+            .to_string()
+    };
+    assert!(
+        error(r#"{"states":[0],"inputs":[3],"outputs":[],"follows":[]}"#)
+            .contains("input 3 not in states")
+    );
+    assert!(
+        error(r#"{"states":[0],"inputs":[],"outputs":[4],"follows":[]}"#)
+            .contains("output 4 not in states")
+    );
+    assert!(
+        error(r#"{"states":[0],"inputs":[],"outputs":[],"follows":[[0,"a",5]]}"#)
+            .contains("unknown to state 5")
+    );
+    assert!(
+        error(r#"{"states":[0],"inputs":[],"outputs":[],"follows":[[6,"a",0]]}"#)
+            .contains("unknown from state 6")
+    );
+    assert!(
+        error(r#"{"states":[0,0],"inputs":[],"outputs":[],"follows":[]}"#)
+            .contains("duplicate state 0")
+    );
+    assert!(
+        error(r#"{"version":99,"states":[],"inputs":[],"outputs":[],"follows":[]}"#)
+            .contains("unsupported format version 99")
+    );
+    assert!(error(
+        r#"{"states":[0],"inputs":[],"outputs":[],"follows":[[0,"z",0]],"alphabet":["a"]}"#
+    )
+    .contains("symbol 'z' not in the alphabet"));
+    assert!(
+        error(r#"{"states":[0],"inputs":[],"outputs":[],"follows":[],"labels":[[1,"x"]]}"#)
+            .contains("of the state 1 not in states")
+    );
+
+    let schema: serde_json::Value = serde_json::from_str(JSON_SCHEMA).unwrap();
+    let properties = schema["properties"].as_object().unwrap();
+    let document: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(document
+        .as_object()
+        .unwrap()
+        .keys()
+        .all(|k| properties.contains_key(k)));
+    assert_eq!(
+        Some(FORMAT_VERSION as u64),
+        properties["version"]["maximum"].as_u64()
+    );
+}
+
+#[test]
+fn reproducible() {
+    for _ in 0..NB_TEST {
+        let r = gen_regex(MAX_DEPTH);
+        let a = Automata::from(r.clone());
+        let b = Automata::from(r);
+        assert_eq!(a.to_dot(false).unwrap(), b.to_dot(false).unwrap());
+        assert_eq!(
+            serde_json::to_string(&a).unwrap(),
+            serde_json::to_string(&b).unwrap()
+        );
+        assert_eq!(a.kosaraju(), b.kosaraju());
+        assert_eq!(
+            a.minimize().to_dot(false).unwrap(),
+            b.minimize().to_dot(false).unwrap()
+        );
+        assert_eq!(
+            a.homogenize().to_dot(false).unwrap(),
+            b.homogenize().to_dot(false).unwrap()
+        );
+    }
+    let a = Automata::from(RegExp::try_from("a.(b+c)*").unwrap());
+    assert_eq!(
+        r#"{"states":[0,1,2,3],"inputs":[0],"outputs":[1,2,3],"follows":[[0,"a",1],[1,"b",2],[1,"c",3],[2,"b",2],[2,"c",3],[3,"b",2],[3,"c",3]],"version":1}"#,
+        serde_json::to_string(&a).unwrap()
+    );
+    assert_eq!(
+        "digraph {
+\trankdir=LR
+\tbgcolor=\"transparent\"
+\tnode [fontname=\"Cantarell\"];
+\tedge [fontname=\"Cantarell\"];
+\t0 [label = \"0\" shape=diamond color=\"purple\"]
+\t1 [label = \"1\" peripheries=2 color=\"purple\"]
+\t2 [label = \"2\" peripheries=2 color=\"purple\"]
+\t3 [label = \"3\" peripheries=2 color=\"purple\"]
+\tsubgraph cluster0 {
+\t\t0
+\t}
+\tsubgraph cluster1 {
+\t\t1
+\t}
+\tsubgraph cluster2 {
+\t\t2
+\t\t3
+\t}
+\t0 -> 1 [label = \"a\"]
+\t1 -> 2 [label = \"b\"]
+\t1 -> 3 [label = \"c\"]
+\t2 -> 2 [label = \"b\"]
+\t2 -> 3 [label = \"c\"]
+\t3 -> 2 [label = \"b\"]
+\t3 -> 3 [label = \"c\"]
+}
+",
+        a.to_dot(false).unwrap()
+    );
+}
+
+/// Renvoie vrai si le code "code" compile avec rustc, les avertissements
+/// étant des erreurs.
+fn compiles(code: &str) -> bool {
+    let dir = std::env::temp_dir().join(format!("glushkovizer-rust-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("lib.rs");
+    std::fs::write(&source, format!("#![deny(warnings)]\n{}", code)).unwrap();
+    let status = std::process::Command::new(std::env::var("RUSTC").unwrap_or("rustc".into()))
+        .args(["--edition", "2021", "--crate-type", "lib", "--out-dir"])
+        .arg(&dir)
+        .arg(&source)
+        .status()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    status.success()
+}
+
+/// Renvoie vrai si "a" a un unique état initial et au plus un successeur par
+/// état et par symbole.
+fn deterministic<V: Eq + Clone>(a: &Automata<char, V>) -> bool {
+    a.inputs_count() == 1
+        && a.states().iter().all(|s| {
+            a.get_follows(s)
+                .unwrap()
+                .iter()
+                .all(|(_, follows)| follows.len() <= 1)
+        })
+}
+
 /// Renvoie un arbre représentant une expression régulière de hauteur maximal
 /// "d".
 pub fn gen_regex(d: usize) -> RegExp<char> {
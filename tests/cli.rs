@@ -0,0 +1,156 @@
+use glushkovizer::prelude::*;
+use glushkovizer::{automata::Automata, regexp::RegExp};
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+/// Lance l'outil avec les arguments "args" et l'entrée standard "input", et
+/// renvoie son code de sortie et sa sortie standard.
+fn run(args: &[&str], input: &[u8]) -> (i32, Vec<u8>) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_glushkovizer"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    let Output { status, stdout, .. } = child.wait_with_output().unwrap();
+    (status.code().unwrap(), stdout)
+}
+
+/// Comme [run], mais renvoie la sortie standard sous forme de texte.
+fn run_text(args: &[&str], input: &str) -> (i32, String) {
+    let (code, stdout) = run(args, input.as_bytes());
+    (code, String::from_utf8(stdout).unwrap())
+}
+
+#[test]
+fn glushkov() {
+    let a = Automata::from(RegExp::try_from("a.(b+c)*").unwrap());
+    assert_eq!(
+        (0, a.to_dot(false).unwrap()),
+        run_text(&["glushkov", "a.(b+c)*"], "")
+    );
+    assert_eq!(
+        (0, a.to_dot(false).unwrap()),
+        run_text(&["glushkov"], "a.(b+c)*\n")
+    );
+    assert_eq!(
+        (0, format!("{}\n", serde_json::to_string(&a).unwrap())),
+        run_text(&["glushkov", "--format", "json", "a.(b+c)*"], "")
+    );
+    assert_eq!(
+        (0, serde_cbor::to_vec(&a).unwrap()),
+        run(&["glushkov", "--format", "cbor", "a.(b+c)*"], b"")
+    );
+    assert_eq!(
+        (0, a.to_svg(true).unwrap()),
+        run_text(&["glushkov", "--format", "svg", "a.(b+c)*"], "")
+    );
+    assert_eq!(2, run_text(&["glushkov", "a.("], "").0);
+    assert_eq!(2, run_text(&["glushkov", "--format", "png", "a"], "").0);
+    assert_eq!(2, run_text(&["unknown"], "").0);
+    assert_eq!(2, run_text(&[], "").0);
+    assert_eq!(0, run_text(&["--help"], "").0);
+}
+
+#[test]
+fn analyses() {
+    let (code, json) = run_text(&["glushkov", "--format", "json", "a.(b+c)*"], "");
+    assert_eq!(0, code);
+    let (code, props) = run_text(&["props"], &json);
+    assert_eq!(0, code);
+    assert!(props.contains("standard: true\n"));
+    assert!(props.contains("homogeneous: true\n"));
+    assert!(props.contains("ambiguous word: none\n"));
+    let (code, props) = run_text(&["props", "--from", "regex"], "(a+a).b");
+    assert_eq!(0, code);
+    assert!(props.contains("unambiguous: false\n"));
+    assert_eq!((0, String::from("2 3\n")), run_text(&["orbits"], &json));
+    assert_eq!(
+        (1, String::from("abc\taccepted\nb\trejected\n\taccepted\n")),
+        run_text(&["accept", "-", "abc", "b", ""], "a*.(b.c)*")
+    );
+    assert_eq!(
+        (0, String::from("a\taccepted\n")),
+        run_text(&["accept", "-", "a"], &json)
+    );
+    assert_eq!(2, run_text(&["accept", "-"], &json).0);
+    let (code, props) = run_text(&["props"], "0\n");
+    assert_eq!(0, code);
+    assert!(props.contains("ambiguous word: none\n"));
+    assert!(props.contains("standard: true\n"));
+    let (code, props) = run_text(&["props", "--from", "att"], "0\n");
+    assert_eq!(0, code);
+    assert!(props.contains("standard: true\n"));
+    let (code, dot) = run_text(
+        &["minimize"],
+        "digraph { i [shape=point] i -> 0 0 -> 1 [label=a] 1 [peripheries=2] }",
+    );
+    assert_eq!(0, code);
+    assert!(dot.starts_with("digraph {"));
+    let (code, json) = run_text(&["determinize", "--format", "json"], "(a+b)*.a");
+    assert_eq!(0, code);
+    let d: Automata<char, usize> = serde_json::from_str(&json).unwrap();
+    assert!(d.states().iter().all(|s| d
+        .get_follows(s)
+        .unwrap()
+        .iter()
+        .all(|(_, follows)| follows.len() == 1)));
+}
+
+#[test]
+fn equiv() {
+    let dir = std::env::temp_dir().join(format!("glushkovizer-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = |name: &str, content: &str| {
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path.to_str().unwrap().to_string()
+    };
+    let a = file("a.txt", "(a+b)*");
+    let b = file("b.txt", "(a*.b*)*");
+    let c = file("c.txt", "(a+b)*.a");
+    assert_eq!(
+        (0, String::from("equivalent\n")),
+        run_text(&["equiv", &a, &b], "")
+    );
+    assert_eq!(
+        (
+            1,
+            String::from("not equivalent: \"\" is only recognized by the first automaton\n")
+        ),
+        run_text(&["equiv", &a, &c], "")
+    );
+    assert_eq!(
+        (
+            1,
+            String::from("not equivalent: \"b\" is only recognized by the second automaton\n")
+        ),
+        run_text(&["equiv", "-", &b], "a*")
+    );
+    assert_eq!(2, run_text(&["equiv", "-", "-"], "a").0);
+    assert_eq!(
+        2,
+        run_text(&["equiv", &a, &dir.join("missing").to_string_lossy()], "").0
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn closed_output() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_glushkovizer"))
+        .args(["orbits", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    drop(child.stdout.take());
+    child.stdin.take().unwrap().write_all(b"a*.b*").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(Some(0), output.status.code());
+    assert!(output.stderr.is_empty());
+}
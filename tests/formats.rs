@@ -0,0 +1,423 @@
+use glushkovizer::prelude::*;
+use glushkovizer::{
+    automata::{
+        error::AutomataError,
+        formats::{
+            from_att, from_dot, from_hoa, from_jflap, to_att, to_hoa, to_jflap, FormatError,
+            Positions,
+        },
+        Automata, DotOptions,
+    },
+    regexp::RegExp,
+};
+use rand::Rng;
+use std::{fmt::Display, hash::Hash};
+
+const MAX_DEPTH: usize = 4;
+const NB_TEST: usize = 10;
+
+#[test]
+fn dot_round_trip() {
+    for _ in 0..NB_TEST {
+        let a = Automata::from(gen_regex(MAX_DEPTH));
+        let expected = content(&a);
+        assert_eq!(
+            expected,
+            content(&from_dot(&a.to_dot(false).unwrap()).unwrap())
+        );
+        assert_eq!(
+            expected,
+            content(&from_dot(&a.to_dot(true).unwrap()).unwrap())
+        );
+        let options = DotOptions {
+            initial_arrows: true,
+            merge_edges: true,
+            value_ids: true,
+            clusters: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            expected,
+            content(&from_dot(&a.to_dot_with(&options).unwrap()).unwrap())
+        );
+    }
+}
+
+#[test]
+fn dot_hand_written() {
+    let a = from_dot(
+        r#"
+# Drawn by hand
+digraph "ab*" {
+    rankdir = LR;
+    node [shape = doublecircle]; q1 q2;
+    node [shape = circle];
+    start [shape = point, label = ""];
+    /* The start state */
+    start -> q0;
+    q0 -> q1 [label = "a"];
+    q1 -> q2 [label = b];
+    q2 -> q2 [ label = "b" ];
+    q0 -> q3 [label = "a, c"] // Dead end
+    subgraph cluster_0 {
+        q3 [label = "sink"]
+    }
+}
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        (
+            vec!["q0", "q1", "q2", "sink"],
+            vec!["q0"],
+            vec!["q1", "q2"],
+            vec![
+                ("q0", "a", "q1"),
+                ("q0", "a", "sink"),
+                ("q0", "c", "sink"),
+                ("q1", "b", "q2"),
+                ("q2", "b", "q2"),
+            ]
+        ),
+        borrowed(&content(&a))
+    );
+    assert!(a.accept(["a", "b", "b"].map(String::from).iter()));
+    assert!(!a.accept(["a", "c"].map(String::from).iter()));
+    assert_eq!(4, a.kosaraju_type().len());
+}
+
+#[test]
+fn dot_errors() {
+    let error = |input: &str| match from_dot(input) {
+        Err(AutomataError::Format(e)) => e,
+        r => panic!("Unexpected result {:?}", r.map(|a| content(&a))),
+    };
+    assert_eq!(
+        FormatError::Syntax {
+            line: 1,
+            column: 1,
+            message: String::from("only directed graphs are supported")
+        },
+        error("graph { a -- b }")
+    );
+    assert_eq!(
+        FormatError::Syntax {
+            line: 2,
+            column: 10,
+            message: String::from("expected '='")
+        },
+        error("digraph {\n\ta [label]\n}")
+    );
+    assert!(matches!(
+        error("digraph { a -> b }"),
+        FormatError::Invalid(_)
+    ));
+    assert!(matches!(
+        error("digraph { a [label = x] b [label = x] }"),
+        FormatError::Invalid(_)
+    ));
+    assert!(matches!(
+        error("digraph { a -> b [label = \"x"),
+        FormatError::Syntax {
+            line: 1,
+            column: 27,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn jflap_round_trip() {
+    for _ in 0..NB_TEST {
+        let a = Automata::from(gen_regex(MAX_DEPTH));
+        let (b, positions) = from_jflap(&to_jflap(&a, &Positions::new()).unwrap()).unwrap();
+        assert_eq!(content(&a), content(&b));
+        assert_eq!(a.states_count(), positions.len());
+    }
+}
+
+#[test]
+fn jflap_positions() {
+    let input = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?><!--Created with JFLAP 7.1.--><structure>&#13;
+	<type>fa</type>&#13;
+	<automaton>&#13;
+		<!--The list of states.-->&#13;
+		<state id="0" name="q0">&#13;
+			<x>62.0</x>&#13;
+			<y>99.0</y>&#13;
+			<initial/>&#13;
+		</state>&#13;
+		<state id="1" name="q1">&#13;
+			<x>186.5</x>&#13;
+			<y>101.0</y>&#13;
+			<final/>&#13;
+		</state>&#13;
+		<state id="2" name="q2">&#13;
+			<label>sink</label>&#13;
+		</state>&#13;
+		<!--The list of transitions.-->&#13;
+		<transition>&#13;
+			<from>0</from>&#13;
+			<to>1</to>&#13;
+			<read>a</read>&#13;
+		</transition>&#13;
+		<transition>&#13;
+			<from>1</from>&#13;
+			<to>1</to>&#13;
+			<read>&lt;b&gt;</read>&#13;
+		</transition>&#13;
+		<transition>&#13;
+			<from>1</from>&#13;
+			<to>2</to>&#13;
+			<read><![CDATA[c]]></read>&#13;
+		</transition>&#13;
+	</automaton>&#13;
+</structure>"#;
+    let (a, positions) = from_jflap(input).unwrap();
+    assert_eq!(
+        (
+            vec!["0", "1", "2"],
+            vec!["0"],
+            vec!["1"],
+            vec![("0", "a", "1"), ("1", "<b>", "1"), ("1", "c", "2")]
+        ),
+        borrowed(&content(&a))
+    );
+    assert_eq!(
+        Positions::from([(0, (62.0, 99.0)), (1, (186.5, 101.0))]),
+        positions
+    );
+    let output = to_jflap(&a, &positions).unwrap();
+    assert!(output.contains("<read>&lt;b&gt;</read>"));
+    let (b, again) = from_jflap(&output).unwrap();
+    assert_eq!(content(&a), content(&b));
+    assert_eq!(
+        positions,
+        again.into_iter().filter(|(s, _)| *s != 2).collect()
+    );
+}
+
+#[test]
+fn jflap_errors() {
+    let error = |input: &str| match from_jflap(input) {
+        Err(AutomataError::Format(e)) => e,
+        r => panic!("Unexpected result {:?}", r.map(|(a, _)| content(&a))),
+    };
+    assert!(matches!(
+        error("<structure><type>pda</type></structure>"),
+        FormatError::Invalid(_)
+    ));
+    assert!(matches!(
+        error(
+            "<structure><type>fa</type><state id=\"0\"/>\
+            <transition><from>0</from><to>0</to><read/></transition></structure>"
+        ),
+        FormatError::Invalid(_)
+    ));
+    assert_eq!(
+        FormatError::Syntax {
+            line: 2,
+            column: 20,
+            message: String::from("expected '</type>', found '</structure>'")
+        },
+        error("<structure>\n<type>fa</structure>")
+    );
+}
+
+#[test]
+fn att() {
+    for _ in 0..NB_TEST {
+        let a = Automata::from(gen_regex(MAX_DEPTH));
+        assert_eq!(
+            content(&a),
+            content(&from_att(&to_att(&a).unwrap()).unwrap())
+        );
+    }
+    let a = from_att("0\t1\ta\t0.5\n1\t1\tb\n1\t2\tc\tInfinity\n\n1\t0\n2\n0 Infinity\n").unwrap();
+    assert_eq!(
+        (
+            vec!["0", "1", "2"],
+            vec!["0"],
+            vec!["1", "2"],
+            vec![("0", "a", "1"), ("1", "b", "1")]
+        ),
+        borrowed(&content(&a))
+    );
+    let b = Automata::new();
+    b.add_state(0usize);
+    b.add_state(1);
+    b.add_input(&1).unwrap();
+    b.add_output(&1).unwrap();
+    b.add_transition(&0, &1, 'a').unwrap();
+    assert_eq!("1\n0\t1\ta\n", to_att(&b).unwrap());
+    b.add_input(&0).unwrap();
+    assert!(matches!(
+        to_att(&b),
+        Err(AutomataError::Format(FormatError::Invalid(_)))
+    ));
+    assert_eq!(
+        FormatError::Syntax {
+            line: 2,
+            column: 3,
+            message: String::from("the state 'x' is not a number")
+        },
+        match from_att("0 1 a\n1 x b") {
+            Err(AutomataError::Format(e)) => e,
+            _ => panic!("Expected an error"),
+        }
+    );
+}
+
+#[test]
+fn hoa() {
+    for _ in 0..NB_TEST {
+        let a = Automata::from(gen_regex(MAX_DEPTH));
+        assert_eq!(
+            content(&a),
+            content(&from_hoa(&to_hoa(&a).unwrap()).unwrap())
+        );
+    }
+    let a = from_hoa(
+        r#"HOA: v1
+name: "a.b*" /* a comment */
+States: 3
+Start: 0
+AP: 2 "a" "b"
+acc-name: Buchi
+Acceptance: 1 Inf(0)
+properties: trans-labels explicit-labels state-acc
+--BODY--
+State: 0 "init"
+[0] 1
+State: 1 {0}
+[1] 1
+State: 2
+[t] 2
+[!0 & !1 | 1&!0] 0
+--END--
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        (
+            vec!["0", "1", "2"],
+            vec!["0"],
+            vec!["1"],
+            vec![
+                ("0", "a", "1"),
+                ("1", "b", "1"),
+                ("2", "a", "2"),
+                ("2", "b", "0"),
+                ("2", "b", "2")
+            ]
+        ),
+        borrowed(&content(&a))
+    );
+    let error = |input: &str| match from_hoa(input) {
+        Err(AutomataError::Format(e)) => e,
+        r => panic!("Unexpected result {:?}", r.map(|a| content(&a))),
+    };
+    assert_eq!(
+        FormatError::Syntax {
+            line: 3,
+            column: 2,
+            message: String::from("parentheses are not supported in labels")
+        },
+        error("HOA: v1 AP: 1 \"a\" --BODY--\nState: 0\n[(0)] 0\n--END--")
+    );
+    assert!(matches!(
+        error("HOA: v1 Start: 0 & 1 --BODY-- --END--"),
+        FormatError::Syntax { .. }
+    ));
+    let a = Automata::from(RegExp::try_from("a.b").unwrap());
+    assert!(to_hoa(&a).unwrap().contains("[0&!1] 1\n"));
+    assert!(matches!(
+        error("HOA: v1 Start: 3 --BODY-- --END--"),
+        FormatError::Invalid(_)
+    ));
+}
+
+/// Renvoie les états, les entrées, les sorties et les transitions de "a",
+/// représentés par des chaînes et triés.
+fn content<'a, T, V>(
+    a: &Automata<'a, T, V>,
+) -> (
+    Vec<String>,
+    Vec<String>,
+    Vec<String>,
+    Vec<(String, String, String)>,
+)
+where
+    T: Eq + Hash + Clone + Display,
+    V: Eq + Clone + Display,
+{
+    let sorted = |l: Vec<V>| {
+        let mut l: Vec<String> = l.iter().map(|v| v.to_string()).collect();
+        l.sort();
+        l
+    };
+    let mut transitions: Vec<(String, String, String)> = a
+        .states()
+        .iter()
+        .flat_map(|from| {
+            a.get_follows(from)
+                .unwrap()
+                .into_iter()
+                .flat_map(move |(symbol, to)| {
+                    to.into_iter()
+                        .map(move |to| (from.to_string(), symbol.to_string(), to.to_string()))
+                })
+        })
+        .collect();
+    transitions.sort();
+    (
+        sorted(a.states()),
+        sorted(a.inputs()),
+        sorted(a.outputs()),
+        transitions,
+    )
+}
+
+/// Renvoie "c" où les chaînes sont empruntées.
+fn borrowed(
+    c: &(
+        Vec<String>,
+        Vec<String>,
+        Vec<String>,
+        Vec<(String, String, String)>,
+    ),
+) -> (Vec<&str>, Vec<&str>, Vec<&str>, Vec<(&str, &str, &str)>) {
+    (
+        strs(&c.0),
+        strs(&c.1),
+        strs(&c.2),
+        c.3.iter()
+            .map(|(f, s, t)| (f.as_str(), s.as_str(), t.as_str()))
+            .collect(),
+    )
+}
+
+/// Renvoie les chaînes de "l" empruntées.
+fn strs(l: &[String]) -> Vec<&str> {
+    l.iter().map(String::as_str).collect()
+}
+
+/// Renvoie un arbre représentant une expression régulière de hauteur maximal
+/// "d".
+pub fn gen_regex(d: usize) -> RegExp<char> {
+    let mut rng = rand::thread_rng();
+    match d {
+        0 => {
+            if rng.gen_bool(0.5) {
+                RegExp::Epsilon
+            } else {
+                RegExp::Symbol(rng.gen_range('a'..'z'))
+            }
+        }
+        _ => match rng.gen_range(0..12) {
+            0..=1 => RegExp::Repeat(Box::new(gen_regex(d - 1))),
+            2..=7 => RegExp::Or(Box::new(gen_regex(d - 1)), Box::new(gen_regex(d - 1))),
+            _ => RegExp::Concat(Box::new(gen_regex(d - 1)), Box::new(gen_regex(d - 1))),
+        },
+    }
+}
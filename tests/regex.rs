@@ -1,4 +1,4 @@
-use glushkovizer::regexp::RegExp;
+use glushkovizer::regexp::{PrettyOptions, RegExp};
 use rand::Rng;
 use std::usize;
 
@@ -18,6 +18,28 @@ fn regex() {
     }
 }
 
+#[test]
+fn pretty() {
+    for i in 0..NB_REGEX {
+        let r = gen_regex(MAX_DEPTH);
+        let s = r.pretty(PrettyOptions::default()).to_string();
+        let r2 = RegExp::try_from(s.as_str());
+        if let Err(s) = r2 {
+            panic!("Error on {}:\n{}\n{}", i, s, r.to_string());
+        }
+        let r2 = r2.unwrap();
+        assert_eq!(r, r2);
+    }
+}
+
+#[test]
+fn whitespace() {
+    let expected = RegExp::try_from("a+b").unwrap();
+    for input in ["a +\nb", "a +\r\nb", "\ta\u{a0}+\u{2003}b\r\n"] {
+        assert_eq!(Ok(expected.clone()), RegExp::try_from(input), "{:?}", input);
+    }
+}
+
 /// Renvoie un arbre représentant une expression régulière de hauteur maximal
 /// "d".
 pub fn gen_regex(d: usize) -> RegExp<char> {